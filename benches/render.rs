@@ -63,6 +63,7 @@ fn run_render() {
         v_up: Vec3::new(0.0, 1.0, 0.0),
         defocus_angle: 10.0,
         focus_dist: 3.4,
+        ..Default::default()
     });
 
     let pixels: Grid<[u8; 3], WIDTH, HEIGHT> = Default::default();
@@ -0,0 +1,118 @@
+use crate::{
+    onb::Onb,
+    ray::Ray,
+    util::{Interval, EMPTY},
+    vec3::Point3,
+};
+
+/// Axis aligned bounding box
+#[derive(Clone, Copy, Debug)]
+pub struct Aabb {
+    pub x: Interval,
+    pub y: Interval,
+    pub z: Interval,
+}
+
+impl Aabb {
+    pub fn new(x: Interval, y: Interval, z: Interval) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Smallest box containing all of `points`
+    pub fn from_points(points: &[Point3]) -> Self {
+        points.iter().fold(Self::default(), |bbox, p| {
+            Self::surrounding(
+                &bbox,
+                &Self::new(
+                    Interval::new(p.x(), p.x()),
+                    Interval::new(p.y(), p.y()),
+                    Interval::new(p.z(), p.z()),
+                ),
+            )
+        })
+    }
+
+    /// Bounds of the box `min`..`max` given in the local coordinates of `frame` placed at
+    /// `origin`
+    pub fn from_local(min: Point3, max: Point3, frame: &Onb, origin: Point3) -> Self {
+        let corners: Vec<Point3> = (0..8)
+            .map(|i| {
+                let local = Point3::new(
+                    if i & 1 == 0 { min.x() } else { max.x() },
+                    if i & 2 == 0 { min.y() } else { max.y() },
+                    if i & 4 == 0 { min.z() } else { max.z() },
+                );
+                origin + frame.transform(&local)
+            })
+            .collect();
+        Self::from_points(&corners)
+    }
+
+    pub fn surrounding(a: &Aabb, b: &Aabb) -> Self {
+        Self {
+            x: Interval::enclosing(&a.x, &b.x),
+            y: Interval::enclosing(&a.y, &b.y),
+            z: Interval::enclosing(&a.z, &b.z),
+        }
+    }
+
+    pub fn axis_interval(&self, n: usize) -> &Interval {
+        match n {
+            1 => &self.y,
+            2 => &self.z,
+            _ => &self.x,
+        }
+    }
+
+    pub fn min(&self) -> Point3 {
+        Point3::new(self.x.min, self.y.min, self.z.min)
+    }
+
+    pub fn max(&self) -> Point3 {
+        Point3::new(self.x.max, self.y.max, self.z.max)
+    }
+
+    pub fn centre(&self) -> Point3 {
+        0.5 * (self.min() + self.max())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.x.min > self.x.max || self.y.min > self.y.max || self.z.min > self.z.max
+    }
+
+    /// Slab test, returning the parametric range of `r` inside the box if it overlaps `ray_t`
+    pub fn hit(&self, r: &Ray, ray_t: Interval) -> Option<Interval> {
+        let origin = r.origin();
+        let direction = r.direction();
+        let origin = [origin.x(), origin.y(), origin.z()];
+        let direction = [direction.x(), direction.y(), direction.z()];
+
+        let mut ray_t = ray_t;
+        for axis in 0..3 {
+            let ax = self.axis_interval(axis);
+            let adinv = 1.0 / direction[axis];
+
+            let t0 = (ax.min - origin[axis]) * adinv;
+            let t1 = (ax.max - origin[axis]) * adinv;
+
+            ray_t.min = ray_t.min.max(t0.min(t1));
+            ray_t.max = ray_t.max.min(t0.max(t1));
+
+            if ray_t.max <= ray_t.min {
+                return None;
+            }
+        }
+
+        Some(ray_t)
+    }
+}
+
+impl Default for Aabb {
+    fn default() -> Self {
+        Self {
+            x: EMPTY,
+            y: EMPTY,
+            z: EMPTY,
+        }
+    }
+}
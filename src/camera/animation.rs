@@ -0,0 +1,100 @@
+use crate::vec3::Point3;
+
+use super::Camera;
+
+/// Camera settings at a point in time along a `CameraPath`
+#[derive(Clone, Copy, Debug)]
+pub struct Keyframe {
+    /// Seconds from the start of the sequence
+    pub time: f64,
+    pub look_from: Point3,
+    pub look_at: Point3,
+    pub v_fov: f64,
+    pub focus_dist: f64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Interpolation {
+    #[default]
+    Linear,
+    /// Smooth curve through every keyframe, which avoids the sudden changes of direction linear
+    /// interpolation has at each one
+    CatmullRom,
+}
+
+/// Keyframed camera motion, with settings between keyframes found by interpolation and held at
+/// the first and last keyframe outside them
+#[derive(Clone, Debug)]
+pub struct CameraPath {
+    keyframes: Vec<Keyframe>,
+    interpolation: Interpolation,
+}
+
+impl CameraPath {
+    pub fn new(mut keyframes: Vec<Keyframe>, interpolation: Interpolation) -> Self {
+        keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Self {
+            keyframes,
+            interpolation,
+        }
+    }
+
+    /// Times of the first and last keyframes
+    pub fn duration(&self) -> Option<(f64, f64)> {
+        Some((self.keyframes.first()?.time, self.keyframes.last()?.time))
+    }
+
+    /// Interpolated settings at `time`, or `None` for a path with no keyframes
+    pub fn at(&self, time: f64) -> Option<Keyframe> {
+        let last = self.keyframes.len().checked_sub(1)?;
+        let next = self.keyframes.partition_point(|k| k.time <= time);
+        if next == 0 {
+            return Some(self.keyframes[0]);
+        }
+        if next > last {
+            return Some(self.keyframes[last]);
+        }
+
+        let (k1, k2) = (&self.keyframes[next - 1], &self.keyframes[next]);
+        let u = (time - k1.time) / (k2.time - k1.time);
+        // Repeat the end keyframes so Catmull-Rom curves still pass through them
+        let k0 = &self.keyframes[next.saturating_sub(2)];
+        let k3 = &self.keyframes[(next + 1).min(last)];
+        let blend = |field: fn(&Keyframe) -> f64| match self.interpolation {
+            Interpolation::Linear => field(k1) + u * (field(k2) - field(k1)),
+            Interpolation::CatmullRom => catmull_rom(field(k0), field(k1), field(k2), field(k3), u),
+        };
+        let point = |x, y, z| Point3::new(blend(x), blend(y), blend(z));
+
+        Some(Keyframe {
+            time,
+            look_from: point(
+                |k| k.look_from.x(),
+                |k| k.look_from.y(),
+                |k| k.look_from.z(),
+            ),
+            look_at: point(|k| k.look_at.x(), |k| k.look_at.y(), |k| k.look_at.z()),
+            v_fov: blend(|k| k.v_fov),
+            focus_dist: blend(|k| k.focus_dist),
+        })
+    }
+
+    /// `base` moved to where the path is at `time`, keeping its other settings
+    pub fn camera_at(&self, time: f64, base: &Camera) -> Camera {
+        let mut options = base.options();
+        if let Some(keyframe) = self.at(time) {
+            options.look_from = keyframe.look_from;
+            options.look_at = keyframe.look_at;
+            options.v_fov = keyframe.v_fov;
+            options.focus_dist = keyframe.focus_dist;
+        }
+        Camera::new(&options)
+    }
+}
+
+fn catmull_rom(p0: f64, p1: f64, p2: f64, p3: f64, u: f64) -> f64 {
+    0.5 * (2.0 * p1
+        + (p2 - p0) * u
+        + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * u * u
+        + (3.0 * p1 - p0 - 3.0 * p2 + p3) * u * u * u)
+}
@@ -0,0 +1,102 @@
+use std::{path::Path, sync::Arc};
+
+use crate::{
+    util::{random_real, PI},
+    vec3::Vec3,
+};
+
+/// Shape of the lens opening, which out of focus highlights take on
+#[derive(Clone, Debug, Default)]
+pub enum Aperture {
+    #[default]
+    Circular,
+    /// Regular polygon formed by `blades` straight blades, rotated by `rotation` degrees
+    Polygon {
+        blades: u32,
+        rotation: f64,
+    },
+    Image(Arc<ApertureImage>),
+}
+
+impl Aperture {
+    /// Random point on the aperture in the xy plane, scaled so the aperture has a radius of 1
+    pub fn sample(&self) -> Vec3 {
+        match self {
+            Aperture::Circular => Vec3::random_in_unit_disk(),
+            Aperture::Polygon { blades, rotation } if *blades >= 3 => {
+                sample_polygon(*blades, rotation.to_radians())
+            }
+            Aperture::Polygon { .. } => Vec3::random_in_unit_disk(),
+            Aperture::Image(image) => image.sample(),
+        }
+    }
+}
+
+/// Picks one of the triangles fanning out from the centre, then a uniform point inside it
+fn sample_polygon(blades: u32, rotation: f64) -> Vec3 {
+    let sector = ((random_real() * blades as f64) as u32).min(blades - 1);
+    let angle = |i: u32| rotation + 2.0 * PI * i as f64 / blades as f64;
+    let a = Vec3::new(angle(sector).cos(), angle(sector).sin(), 0.0);
+    let b = Vec3::new(angle(sector + 1).cos(), angle(sector + 1).sin(), 0.0);
+
+    let (mut s, mut t) = (random_real(), random_real());
+    if s + t > 1.0 {
+        (s, t) = (1.0 - s, 1.0 - t);
+    }
+    s * a + t * b
+}
+
+/// Aperture mask loaded from an image, where brighter pixels let through more light. The
+/// image is fitted to the unit square around the lens axis
+#[derive(Clone, Debug)]
+pub struct ApertureImage {
+    width: u32,
+    height: u32,
+    /// Running total of pixel weights in row major order
+    cdf: Vec<f64>,
+}
+
+impl ApertureImage {
+    pub fn from<P>(filename: P) -> anyhow::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let image = image::open(filename)?.to_luma8();
+
+        let mut total = 0.0;
+        let cdf: Vec<f64> = image
+            .pixels()
+            .map(|pixel| {
+                total += pixel[0] as f64 / 255.0;
+                total
+            })
+            .collect();
+        if total <= 0.0 {
+            anyhow::bail!("aperture image is completely black");
+        }
+
+        Ok(Self {
+            width: image.width(),
+            height: image.height(),
+            cdf,
+        })
+    }
+
+    fn sample(&self) -> Vec3 {
+        let target = random_real() * self.cdf[self.cdf.len() - 1];
+        let index = self
+            .cdf
+            .partition_point(|c| *c <= target)
+            .min(self.cdf.len() - 1);
+
+        let x = (index as u32 % self.width) as f64 + random_real();
+        let y = (index as u32 / self.width) as f64 + random_real();
+        let size = self.width.max(self.height) as f64;
+        // Flip y so the image is upright when looking through the lens
+        Vec3::new(
+            (2.0 * x - self.width as f64) / size,
+            (self.height as f64 - 2.0 * y) / size,
+            0.0,
+        )
+    }
+}
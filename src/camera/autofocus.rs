@@ -0,0 +1,48 @@
+use crate::{
+    hittable::{Hittable, HittableList},
+    ray::Ray,
+    util::Interval,
+    vec3::dot,
+};
+
+use super::Camera;
+
+/// How the camera picks its focus distance from the scene before rendering
+#[derive(Clone, Debug, PartialEq)]
+pub enum AutoFocus {
+    /// Focus on whatever is visible through the centre of pixel (x, y). For stereo renders
+    /// this is a pixel of the whole output image, either eye's half
+    Pixel { x: usize, y: usize },
+    /// Focus on an object added to the world with `HittableList::add_named`
+    Object(String),
+}
+
+impl AutoFocus {
+    /// Distance along the view direction to the focus target, or `None` if there's nothing
+    /// there to focus on
+    pub fn distance(&self, camera: &Camera, world: &HittableList) -> Option<f64> {
+        let target = match self {
+            AutoFocus::Pixel { x, y } => {
+                // Cast from the centre of the lens so defocus blur can't move the target
+                let mut pinhole = camera.clone();
+                pinhole.defocus_angle = 0.0;
+                let s = (*x as f64 + 0.5) / camera.image_width as f64;
+                let t = (*y as f64 + 0.5) / camera.image_height as f64;
+                let ray = pinhole.model.central_ray(&pinhole, s, t)?;
+                world.hit(&ray, Interval::new(0.001, f64::INFINITY))?.p
+            }
+            AutoFocus::Object(name) => {
+                let object = world.named(name)?;
+                let centre = object.bounding_box().centre();
+                let ray = Ray::new(camera.centre(), centre - camera.centre());
+                // Aim for the visible surface, falling back to the middle of the object
+                object
+                    .hit(&ray, Interval::new(0.001, f64::INFINITY))
+                    .map_or(centre, |hit| hit.p)
+            }
+        };
+
+        let distance = dot(&(target - camera.centre()), &-camera.w());
+        (distance > 0.0).then_some(distance)
+    }
+}
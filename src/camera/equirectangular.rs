@@ -0,0 +1,19 @@
+use crate::{ray::Ray, util::PI};
+
+use super::{Camera, CameraModel};
+
+/// Full 360 by 180 degree latitude-longitude panorama centred on the view direction, best
+/// rendered at a 2:1 aspect ratio
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Equirectangular;
+
+impl CameraModel for Equirectangular {
+    fn ray(&self, camera: &Camera, s: f64, t: f64) -> Option<(Ray, f64)> {
+        let longitude = (s - 0.5) * 2.0 * PI;
+        let latitude = (0.5 - t) * PI;
+
+        let direction = latitude.cos() * longitude.sin() * camera.u() + latitude.sin() * camera.v()
+            - latitude.cos() * longitude.cos() * camera.w();
+        Some((Ray::new(camera.centre(), direction), 1.0))
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{ray::Ray, util::degrees_to_radians};
+
+use super::{Camera, CameraModel};
+
+/// How the angle from the view direction maps to distance from the centre of the image circle
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FisheyeMapping {
+    /// Distance proportional to angle
+    Equidistant,
+    /// Distance proportional to `sin(angle / 2)`, preserving solid angle
+    Equisolid,
+}
+
+/// Circular fisheye with the image circle fitting the shorter side of the image. Pixels
+/// outside the circle receive no rays
+#[derive(Clone, Copy, Debug)]
+pub struct Fisheye {
+    half_fov: f64,
+    mapping: FisheyeMapping,
+    aspect_ratio: f64,
+}
+
+impl Fisheye {
+    /// `fov` in degrees across the full image circle, which may exceed 180
+    pub fn new(fov: f64, mapping: FisheyeMapping, aspect_ratio: f64) -> Self {
+        Self {
+            half_fov: degrees_to_radians(fov.clamp(1.0, 360.0)) / 2.0,
+            mapping,
+            aspect_ratio,
+        }
+    }
+}
+
+impl CameraModel for Fisheye {
+    fn ray(&self, camera: &Camera, s: f64, t: f64) -> Option<(Ray, f64)> {
+        // Position on the image relative to a unit image circle
+        let mut x = 2.0 * s - 1.0;
+        let mut y = 1.0 - 2.0 * t;
+        if self.aspect_ratio > 1.0 {
+            x *= self.aspect_ratio;
+        } else {
+            y /= self.aspect_ratio;
+        }
+        let r = (x * x + y * y).sqrt();
+        if r > 1.0 {
+            return None;
+        }
+
+        let theta = match self.mapping {
+            FisheyeMapping::Equidistant => r * self.half_fov,
+            FisheyeMapping::Equisolid => {
+                2.0 * (r * (self.half_fov / 2.0).sin()).clamp(-1.0, 1.0).asin()
+            }
+        };
+        let (cos_phi, sin_phi) = if r > 0.0 { (x / r, y / r) } else { (1.0, 0.0) };
+
+        let direction =
+            theta.sin() * (cos_phi * camera.u() + sin_phi * camera.v()) - theta.cos() * camera.w();
+        Some((Ray::new(camera.centre(), direction), 1.0))
+    }
+}
@@ -0,0 +1,437 @@
+use std::{path::Path, sync::Arc};
+
+use rayon::prelude::*;
+use tracing::warn;
+
+use crate::{
+    ray::Ray,
+    util::{random_real, Interval},
+    vec3::{dot, Point3, Vec3},
+};
+
+use super::{Camera, CameraModel};
+
+/// Number of rings across the film that exit pupil bounds are cached for
+const EXIT_PUPIL_SEGMENTS: usize = 64;
+/// Film positions and rear element grid size sampled per ring when bounding the exit pupil
+const PUPIL_FILM_SAMPLES: usize = 16;
+const PUPIL_GRID_SAMPLES: usize = 48;
+
+/// One refracting surface of a lens, in metres. A `curvature_radius` of zero marks the
+/// aperture stop
+#[derive(Clone, Copy, Debug)]
+pub struct LensElement {
+    /// Positive when the centre of curvature is on the film side
+    pub curvature_radius: f64,
+    /// Distance along the axis to the next surface towards the film
+    pub thickness: f64,
+    /// Refractive index of the medium between this surface and the next towards the film
+    pub ior: f64,
+    pub aperture_radius: f64,
+}
+
+impl LensElement {
+    fn is_stop(&self) -> bool {
+        self.curvature_radius == 0.0
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct LensSystemOptions {
+    /// Film diagonal in mm, which together with the lens sets the field of view
+    pub film_diagonal: f64,
+    /// Distance from the film to the plane in focus, in metres. The camera's `auto_focus`
+    /// overrides it by moving the film again
+    pub focus_distance: f64,
+    /// Stopped down aperture diameter in mm, limited to the stop's size in the prescription
+    pub aperture_diameter: Option<f64>,
+}
+
+impl Default for LensSystemOptions {
+    fn default() -> Self {
+        Self {
+            film_diagonal: 35.0,
+            focus_distance: 10.0,
+            aperture_diameter: None,
+        }
+    }
+}
+
+/// Camera that traces rays from the film through every element of a real lens design, so
+/// vignetting, distortion and focus breathing come from the lens itself. World units are
+/// taken to be metres, with the film at the camera centre
+#[derive(Clone, Debug)]
+pub struct LensSystem {
+    /// Surfaces from the front of the lens to the rear, the last thickness being the distance
+    /// from the rear surface to the film
+    elements: Vec<LensElement>,
+    film_diagonal: f64,
+    /// Bounds on the rear element plane that rays from each ring of the film can pass through,
+    /// measured for film points on the +x axis
+    exit_pupil: Vec<Option<(Interval, Interval)>>,
+    /// Irradiance at the centre of the film, which sample weights are divided by so an evenly
+    /// lit scene comes out at the same brightness as with the other projections
+    on_axis_irradiance: f64,
+}
+
+impl LensSystem {
+    /// Reads a prescription with one surface per line, front to back, as whitespace separated
+    /// curvature radius, thickness, index of refraction and aperture diameter, all in mm.
+    /// Lines starting with `#` are comments, and an index of 0 means air
+    pub fn from_file<P>(filename: P, options: &LensSystemOptions) -> anyhow::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let text = std::fs::read_to_string(filename)?;
+
+        let mut elements = Vec::new();
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let values = line
+                .split_whitespace()
+                .map(str::parse::<f64>)
+                .collect::<Result<Vec<_>, _>>()?;
+            let [radius, thickness, ior, aperture] = values[..] else {
+                anyhow::bail!("expected 4 values per lens surface, got \"{line}\"");
+            };
+            elements.push(LensElement {
+                curvature_radius: radius / 1000.0,
+                thickness: thickness / 1000.0,
+                ior: if ior == 0.0 { 1.0 } else { ior },
+                aperture_radius: aperture / 2000.0,
+            });
+        }
+
+        Self::new(elements, options)
+    }
+
+    /// Focuses the lens by moving the film, then caches the exit pupil
+    pub fn new(
+        mut elements: Vec<LensElement>,
+        options: &LensSystemOptions,
+    ) -> anyhow::Result<Self> {
+        if elements.is_empty() {
+            anyhow::bail!("lens has no surfaces");
+        }
+        if let Some(diameter) = options.aperture_diameter {
+            let Some(stop) = elements.iter_mut().find(|e| e.is_stop()) else {
+                anyhow::bail!("lens has no aperture stop");
+            };
+            stop.aperture_radius = stop.aperture_radius.min(diameter / 2000.0);
+        }
+        focus(&mut elements, options.focus_distance)?;
+
+        Self::with_focused_elements(elements, options.film_diagonal / 1000.0)
+    }
+
+    /// Caches the exit pupil for `elements` already focused, with the film diagonal in metres
+    fn with_focused_elements(
+        elements: Vec<LensElement>,
+        film_diagonal: f64,
+    ) -> anyhow::Result<Self> {
+        let exit_pupil = (0..EXIT_PUPIL_SEGMENTS)
+            .into_par_iter()
+            .map(|i| {
+                let radius = |i: usize| i as f64 / EXIT_PUPIL_SEGMENTS as f64 * film_diagonal / 2.0;
+                bound_exit_pupil(&elements, radius(i), radius(i + 1))
+            })
+            .collect::<Vec<_>>();
+
+        let on_axis_irradiance = exit_pupil[0]
+            .map(|bounds| on_axis_irradiance(&elements, bounds))
+            .unwrap_or(0.0);
+        if on_axis_irradiance <= 0.0 {
+            anyhow::bail!("no light reaches the centre of the film");
+        }
+
+        Ok(Self {
+            elements,
+            film_diagonal,
+            exit_pupil,
+            on_axis_irradiance,
+        })
+    }
+
+    /// Ray and weight for image point (s, t) through the point `pupil` of the exit pupil
+    /// bounds, given as fractions of their width and height
+    fn trace(&self, camera: &Camera, s: f64, t: f64, pupil: (f64, f64)) -> Option<(Ray, f64)> {
+        let aspect_ratio = camera.image_width as f64 / camera.image_height as f64;
+        let half_height = self.film_diagonal / 2.0 / (1.0 + aspect_ratio * aspect_ratio).sqrt();
+        let half_width = half_height * aspect_ratio;
+
+        // The lens forms an inverted image, so the film is flipped relative to the output
+        let film = Point3::new(
+            (1.0 - 2.0 * s) * half_width,
+            (2.0 * t - 1.0) * half_height,
+            0.0,
+        );
+        let film_radius = (film.x() * film.x() + film.y() * film.y()).sqrt();
+        let segment = ((film_radius / (self.film_diagonal / 2.0) * EXIT_PUPIL_SEGMENTS as f64)
+            as usize)
+            .min(EXIT_PUPIL_SEGMENTS - 1);
+        let (x_bounds, y_bounds) = self.exit_pupil[segment]?;
+
+        // Sample the pupil bounds, rotated round from the +x axis to the film point
+        let x = x_bounds.min + pupil.0 * x_bounds.size();
+        let y = y_bounds.min + pupil.1 * y_bounds.size();
+        let (sin, cos) = if film_radius > 0.0 {
+            (film.y() / film_radius, film.x() / film_radius)
+        } else {
+            (0.0, 1.0)
+        };
+        let rear = Point3::new(
+            x * cos - y * sin,
+            x * sin + y * cos,
+            -rear_z(&self.elements),
+        );
+        let direction = rear - film;
+
+        let exit = trace_from_film(&self.elements, &Ray::new(film, direction))?;
+
+        // The pupil bounds are sampled uniformly, so weight by their area and the cos^4 falloff
+        // of light arriving at an angle to the film
+        let cos_theta = direction.z().abs() / direction.length();
+        let weight = x_bounds.size() * y_bounds.size() * cos_theta.powi(4)
+            / (rear_z(&self.elements).powi(2) * self.on_axis_irradiance);
+
+        // Lens space has the film at the origin looking down -z, which lines up with the
+        // camera's u, v, w basis
+        let to_world = |d: Vec3| d.x() * camera.u() + d.y() * camera.v() + d.z() * camera.w();
+        Some((
+            Ray::new(
+                camera.centre() + to_world(exit.origin()),
+                to_world(exit.direction()),
+            ),
+            weight,
+        ))
+    }
+}
+
+impl CameraModel for LensSystem {
+    fn ray(&self, camera: &Camera, s: f64, t: f64) -> Option<(Ray, f64)> {
+        self.trace(camera, s, t, (random_real(), random_real()))
+    }
+
+    /// Aims for the middle of the exit pupil, falling back to random samples if the lens
+    /// blocks it
+    fn central_ray(&self, camera: &Camera, s: f64, t: f64) -> Option<Ray> {
+        self.trace(camera, s, t, (0.5, 0.5))
+            .or_else(|| (0..PUPIL_GRID_SAMPLES).find_map(|_| self.ray(camera, s, t)))
+            .map(|(ray, _)| ray)
+    }
+
+    /// Moves the film, keeping the current focus with a warning if the lens can't reach
+    /// `distance`
+    fn refocused(&self, distance: f64) -> Option<Arc<dyn CameraModel>> {
+        let mut elements = self.elements.clone();
+        let refocused = focus(&mut elements, distance)
+            .and_then(|_| Self::with_focused_elements(elements, self.film_diagonal));
+        match refocused {
+            Ok(lens) => Some(Arc::new(lens)),
+            Err(err) => {
+                warn!("can't refocus lens at {distance}m: {err}");
+                None
+            }
+        }
+    }
+}
+
+fn rear_z(elements: &[LensElement]) -> f64 {
+    elements[elements.len() - 1].thickness
+}
+
+/// Follows a ray in lens space from the film out of the front of the lens, or `None` if
+/// it's blocked by an element or the stop
+fn trace_from_film(elements: &[LensElement], r: &Ray) -> Option<Ray> {
+    let mut ray = Ray::new(r.origin(), r.direction().unit_vector());
+    let mut z = 0.0;
+    for (i, element) in elements.iter().enumerate().rev() {
+        z -= element.thickness;
+
+        let (t, normal) = if element.is_stop() {
+            let t = (z - ray.origin().z()) / ray.direction().z();
+            if !t.is_finite() || t < 0.0 {
+                return None;
+            }
+            (t, None)
+        } else {
+            let (t, normal) = intersect_surface(element.curvature_radius, z, &ray)?;
+            (t, Some(normal))
+        };
+
+        let p = ray.at(t);
+        if p.x() * p.x() + p.y() * p.y() > element.aperture_radius * element.aperture_radius {
+            return None;
+        }
+
+        let direction = match normal {
+            Some(normal) => {
+                let outside_ior = if i > 0 { elements[i - 1].ior } else { 1.0 };
+                refract(&ray.direction(), &normal, element.ior / outside_ior)?
+            }
+            None => ray.direction(),
+        };
+        ray = Ray::new(p, direction);
+    }
+    Some(ray)
+}
+
+/// Hits the spherical surface with its vertex at `z`, returning the distance and a normal
+/// facing back along the ray
+fn intersect_surface(radius: f64, z: f64, r: &Ray) -> Option<(f64, Vec3)> {
+    let oc = r.origin() - Point3::new(0.0, 0.0, z + radius);
+    let a = r.direction().length_squared();
+    let h = dot(&r.direction(), &oc);
+    let c = oc.length_squared() - radius * radius;
+
+    let discriminant = h * h - a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let sqrtd = discriminant.sqrt();
+
+    // Only one side of the sphere is part of the lens, which depends on which way it curves
+    let use_nearer = (r.direction().z() > 0.0) ^ (radius < 0.0);
+    let t = if use_nearer {
+        (-h - sqrtd) / a
+    } else {
+        (-h + sqrtd) / a
+    };
+    if t < 0.0 {
+        return None;
+    }
+
+    let normal = (oc + t * r.direction()).unit_vector();
+    let normal = if dot(&normal, &r.direction()) > 0.0 {
+        -normal
+    } else {
+        normal
+    };
+    Some((t, normal))
+}
+
+/// Refracts unit direction `d` through a surface with normal `n` facing against it, or `None`
+/// on total internal reflection
+fn refract(d: &Vec3, n: &Vec3, eta: f64) -> Option<Vec3> {
+    let cos_i = -dot(d, n);
+    let sin2_t = eta * eta * (1.0 - cos_i * cos_i);
+    if sin2_t > 1.0 {
+        return None;
+    }
+    let cos_t = (1.0 - sin2_t).sqrt();
+    Some(eta * *d + (eta * cos_i - cos_t) * *n)
+}
+
+/// Where a ray from the centre of the film just off the axis crosses back over the axis in
+/// front of the lens, or `None` if the lens doesn't bring it to a focus
+fn focus_distance(elements: &[LensElement]) -> Option<f64> {
+    let rear = elements[elements.len() - 1];
+    [0.1, 0.01, 0.001].into_iter().find_map(|scale| {
+        let r = Ray::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(scale * rear.aperture_radius, 0.0, -rear.thickness),
+        );
+        let exit = trace_from_film(elements, &r)?;
+        let t = -exit.origin().x() / exit.direction().x();
+        let z = exit.at(t).z();
+        (t.is_finite() && t > 0.0).then_some(-z)
+    })
+}
+
+/// Sets the distance from the rear element to the film so the lens focuses at
+/// `focus_distance`, searching outwards from the prescription's own spacing
+fn focus(elements: &mut [LensElement], target: f64) -> anyhow::Result<()> {
+    let last = elements.len() - 1;
+    let distance_at = |elements: &mut [LensElement], film_distance: f64| {
+        elements[last].thickness = film_distance;
+        focus_distance(elements).unwrap_or(f64::INFINITY)
+    };
+
+    // Bracket the film distance between one focused too far and one focused too near
+    let mut lo = elements[last].thickness.max(0.0);
+    while distance_at(elements, lo) <= target {
+        lo *= 0.5;
+        if lo < 1e-6 {
+            anyhow::bail!("lens can't focus as far as {target}m");
+        }
+    }
+    let mut step = lo.max(1e-3);
+    let mut hi = lo + step;
+    while distance_at(elements, hi) > target {
+        step *= 2.0;
+        hi = lo + step;
+        if step > 1.0 {
+            anyhow::bail!("lens can't focus as near as {target}m");
+        }
+    }
+
+    for _ in 0..64 {
+        let mid = 0.5 * (lo + hi);
+        if distance_at(elements, mid) > target {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    elements[last].thickness = 0.5 * (lo + hi);
+    Ok(())
+}
+
+/// Traces a grid of rays from film points between `r0` and `r1` along the +x axis to the rear
+/// element plane, bounding the ones that make it out of the lens
+fn bound_exit_pupil(elements: &[LensElement], r0: f64, r1: f64) -> Option<(Interval, Interval)> {
+    let rear = elements[elements.len() - 1];
+    let extent = 1.5 * rear.aperture_radius;
+    let spacing = 2.0 * extent / PUPIL_GRID_SAMPLES as f64;
+
+    let mut x_bounds = Interval::default();
+    let mut y_bounds = Interval::default();
+    for i in 0..PUPIL_FILM_SAMPLES {
+        let film_x = r0 + (i as f64 + 0.5) / PUPIL_FILM_SAMPLES as f64 * (r1 - r0);
+        let film = Point3::new(film_x, 0.0, 0.0);
+        for j in 0..PUPIL_GRID_SAMPLES * PUPIL_GRID_SAMPLES {
+            let x = -extent + ((j % PUPIL_GRID_SAMPLES) as f64 + 0.5) * spacing;
+            let y = -extent + ((j / PUPIL_GRID_SAMPLES) as f64 + 0.5) * spacing;
+            let rear_point = Point3::new(x, y, -rear.thickness);
+            if trace_from_film(elements, &Ray::new(film, rear_point - film)).is_some() {
+                x_bounds = Interval::enclosing(&x_bounds, &Interval::new(x, x));
+                y_bounds = Interval::enclosing(&y_bounds, &Interval::new(y, y));
+            }
+        }
+    }
+    if x_bounds.size() < 0.0 {
+        return None;
+    }
+
+    // Pad by a grid cell so rays just outside the sampled points aren't missed
+    Some((
+        Interval::new(x_bounds.min - spacing, x_bounds.max + spacing),
+        Interval::new(y_bounds.min - spacing, y_bounds.max + spacing),
+    ))
+}
+
+/// Integrates the cos^4 falloff over the parts of the exit pupil `bounds` that let light
+/// through to the centre of the film
+fn on_axis_irradiance(elements: &[LensElement], (x_bounds, y_bounds): (Interval, Interval)) -> f64 {
+    let z = rear_z(elements);
+    let cell_area =
+        x_bounds.size() * y_bounds.size() / (PUPIL_GRID_SAMPLES * PUPIL_GRID_SAMPLES) as f64;
+    let film = Point3::new(0.0, 0.0, 0.0);
+
+    (0..PUPIL_GRID_SAMPLES * PUPIL_GRID_SAMPLES)
+        .filter_map(|j| {
+            let fx = ((j % PUPIL_GRID_SAMPLES) as f64 + 0.5) / PUPIL_GRID_SAMPLES as f64;
+            let fy = ((j / PUPIL_GRID_SAMPLES) as f64 + 0.5) / PUPIL_GRID_SAMPLES as f64;
+            let rear = Point3::new(
+                x_bounds.min + fx * x_bounds.size(),
+                y_bounds.min + fy * y_bounds.size(),
+                -z,
+            );
+            trace_from_film(elements, &Ray::new(film, rear - film))?;
+            let cos_theta = z / (rear - film).length();
+            Some(cell_area * cos_theta.powi(4) / (z * z))
+        })
+        .sum()
+}
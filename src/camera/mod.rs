@@ -0,0 +1,293 @@
+use std::{fmt::Debug, sync::Arc};
+
+use crate::{
+    hittable::HittableList,
+    mod_flat,
+    ray::Ray,
+    util::{degrees_to_radians, random_real},
+    vec3::{cross, Point3, Vec3},
+};
+
+mod_flat!(animation aperture autofocus equirectangular fisheye lens_system orthographic perspective physical stereo);
+
+/// Maps a point on the image to a ray leaving the camera
+pub trait CameraModel: Sync + Send + Debug {
+    /// `s` and `t` are normalised image coordinates running from 0 to 1 left to right and top
+    /// to bottom. Returns the ray with the weight its colour is scaled by, or `None` for
+    /// points the model doesn't cover
+    fn ray(&self, camera: &Camera, s: f64, t: f64) -> Option<(Ray, f64)>;
+
+    /// Ray through the centre of the lens for image point (s, t), used to find what the camera
+    /// is looking at there. Models whose only lens is the camera's defocus disk just use `ray`
+    fn central_ray(&self, camera: &Camera, s: f64, t: f64) -> Option<Ray> {
+        self.ray(camera, s, t).map(|(ray, _)| ray)
+    }
+
+    /// Copy of a model that focuses itself, such as a real lens, refocused at `distance`.
+    /// `None` for models that take their focus from the camera's `focus_dist`
+    fn refocused(&self, #[allow(unused_variables)] distance: f64) -> Option<Arc<dyn CameraModel>> {
+        None
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Projection {
+    /// Thin lens perspective using `v_fov`, `defocus_angle` and `focus_dist`
+    Perspective,
+    /// Parallel rays from a viewport `viewport_height` world units tall, with no depth of field
+    Orthographic {
+        viewport_height: f64,
+    },
+    /// 360 degree latitude-longitude panorama
+    Equirectangular,
+    /// Circular fisheye covering `fov` degrees
+    Fisheye {
+        fov: f64,
+        mapping: FisheyeMapping,
+    },
+    Custom(Arc<dyn CameraModel>),
+}
+
+#[derive(Clone, Debug)]
+pub struct CameraOptions {
+    pub aspect_ratio: f64,
+    pub image_width: i32,
+    pub image_height: i32,
+    pub v_fov: f64,
+    pub look_from: Point3,
+    pub look_at: Point3,
+    pub v_up: Vec3,
+    pub defocus_angle: f64,
+    pub focus_dist: f64,
+    pub projection: Projection,
+    /// Linear scale applied to radiance before tone mapping
+    pub exposure: f64,
+    pub aperture: Aperture,
+    /// Horizontal squeeze of an anamorphic lens, which stretches out of focus highlights
+    /// vertically by the same factor. 1 for a spherical lens
+    pub anamorphic_squeeze: f64,
+    /// Perspective viewport offset as fractions of its width and height, positive right and up
+    pub lens_shift: (f64, f64),
+    /// Perspective focal plane tilt about the horizontal and swing about the vertical axis in
+    /// degrees, see `Perspective::with_tilt`
+    pub focal_plane_tilt: (f64, f64),
+    /// Sets `focus_dist` from the scene when rendering, leaving it unchanged if nothing is found
+    pub auto_focus: Option<AutoFocus>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Camera {
+    pub aspect_ratio: f64,
+    pub image_width: i32,
+    pub image_height: i32,
+    pub v_fov: f64,
+    pub look_from: Point3,
+    pub look_at: Point3,
+    pub v_up: Vec3,
+    pub defocus_angle: f64,
+    pub focus_dist: f64,
+    pub projection: Projection,
+    pub exposure: f64,
+    pub aperture: Aperture,
+    pub anamorphic_squeeze: f64,
+    pub lens_shift: (f64, f64),
+    pub focal_plane_tilt: (f64, f64),
+    pub auto_focus: Option<AutoFocus>,
+    model: Arc<dyn CameraModel>,
+    centre: Point3,
+    u: Vec3,
+    v: Vec3,
+    w: Vec3,
+    defocus_disk_u: Vec3,
+    defocus_disk_v: Vec3,
+}
+
+impl Camera {
+    pub fn new(options: &CameraOptions) -> Self {
+        let mut res = Self {
+            aspect_ratio: options.aspect_ratio,
+            image_width: options.image_width,
+            image_height: options.image_height,
+            v_fov: options.v_fov,
+            look_from: options.look_from,
+            look_at: options.look_at,
+            v_up: options.v_up,
+            defocus_angle: options.defocus_angle,
+            focus_dist: options.focus_dist,
+            projection: options.projection.clone(),
+            exposure: options.exposure,
+            aperture: options.aperture.clone(),
+            anamorphic_squeeze: options.anamorphic_squeeze,
+            lens_shift: options.lens_shift,
+            focal_plane_tilt: options.focal_plane_tilt,
+            auto_focus: options.auto_focus.clone(),
+            ..Default::default()
+        };
+        res.initialise();
+        res
+    }
+    /// The options this camera was built from
+    pub fn options(&self) -> CameraOptions {
+        CameraOptions {
+            aspect_ratio: self.aspect_ratio,
+            image_width: self.image_width,
+            image_height: self.image_height,
+            v_fov: self.v_fov,
+            look_from: self.look_from,
+            look_at: self.look_at,
+            v_up: self.v_up,
+            defocus_angle: self.defocus_angle,
+            focus_dist: self.focus_dist,
+            projection: self.projection.clone(),
+            exposure: self.exposure,
+            aperture: self.aperture.clone(),
+            anamorphic_squeeze: self.anamorphic_squeeze,
+            lens_shift: self.lens_shift,
+            focal_plane_tilt: self.focal_plane_tilt,
+            auto_focus: self.auto_focus.clone(),
+        }
+    }
+    pub fn width(&self) -> i32 {
+        self.image_width
+    }
+    pub fn height(&self) -> i32 {
+        self.image_height
+    }
+    pub fn centre(&self) -> Point3 {
+        self.centre
+    }
+    pub fn u(&self) -> Vec3 {
+        self.u
+    }
+    pub fn v(&self) -> Vec3 {
+        self.v
+    }
+    pub fn w(&self) -> Vec3 {
+        self.w
+    }
+    /// Jittered ray through pixel (x, y) and the weight of its sample, or `None` if the
+    /// projection doesn't cover it
+    pub fn hit_ray(&self, x: usize, y: usize) -> Option<(Ray, f64)> {
+        let offset = sample_square();
+
+        let s = (x as f64 + 0.5 + offset.x()) / self.image_width as f64;
+        let t = (y as f64 + 0.5 + offset.y()) / self.image_height as f64;
+        self.model.ray(self, s, t)
+    }
+    /// Refocuses according to `auto_focus`, if set, by casting into `world`. Models that focus
+    /// themselves, such as a `LensSystem`, are replaced with a refocused copy
+    pub fn focus_on(&mut self, world: &HittableList) {
+        let Some(auto_focus) = &self.auto_focus else {
+            return;
+        };
+        if let Some(distance) = auto_focus.distance(self, world) {
+            self.focus_dist = distance;
+            if let Some(model) = self.model.refocused(distance) {
+                self.projection = Projection::Custom(model);
+            }
+            self.initialise();
+        }
+    }
+    /// Origin for a ray leaving the lens, which is the camera centre unless there is defocus
+    /// blur
+    pub fn lens_sample(&self) -> Point3 {
+        if self.defocus_angle <= 0.0 {
+            self.centre
+        } else {
+            self.defocus_disk_sample()
+        }
+    }
+    fn initialise(&mut self) {
+        self.centre = self.look_from;
+
+        let aspect_ratio = self.image_width as f64 / self.image_height as f64;
+        self.model = match &self.projection {
+            Projection::Perspective => Arc::new(
+                Perspective::new(self.v_fov, aspect_ratio)
+                    .with_shift(self.lens_shift.0, self.lens_shift.1)
+                    .with_tilt(self.focal_plane_tilt.0, self.focal_plane_tilt.1),
+            ),
+            Projection::Orthographic { viewport_height } => {
+                Arc::new(Orthographic::new(*viewport_height, aspect_ratio))
+            }
+            Projection::Equirectangular => Arc::new(Equirectangular),
+            Projection::Fisheye { fov, mapping } => {
+                Arc::new(Fisheye::new(*fov, *mapping, aspect_ratio))
+            }
+            Projection::Custom(model) => model.clone(),
+        };
+
+        // Calculate the u,v,w unit basis vectors for the camera coordinate frame
+        self.w = (self.look_from - self.look_at).unit_vector();
+        self.u = (cross(&self.v_up, &self.w)).unit_vector();
+        self.v = cross(&self.w, &self.u);
+
+        // Calculate the camera defocus disk basis vectors
+        let defocus_radius = self.focus_dist * (degrees_to_radians(self.defocus_angle / 2.0)).tan();
+        self.defocus_disk_u = self.u * defocus_radius;
+        self.defocus_disk_v = self.v * defocus_radius;
+    }
+
+    fn defocus_disk_sample(&self) -> Point3 {
+        let p = self.aperture.sample();
+        self.centre
+            + (p.x() / self.anamorphic_squeeze * self.defocus_disk_u)
+            + (p.y() * self.defocus_disk_v)
+    }
+}
+
+impl Default for CameraOptions {
+    fn default() -> Self {
+        Self {
+            aspect_ratio: 1.0,
+            image_width: 100,
+            image_height: 100,
+            v_fov: 90.0,
+            look_from: Point3::new(0.0, 0.0, 0.0),
+            look_at: Point3::new(0.0, 0.0, -1.0),
+            v_up: Vec3::new(0.0, 1.0, 0.0),
+            defocus_angle: 0.0,
+            focus_dist: 10.0,
+            projection: Projection::Perspective,
+            exposure: 1.0,
+            aperture: Aperture::Circular,
+            anamorphic_squeeze: 1.0,
+            lens_shift: (0.0, 0.0),
+            focal_plane_tilt: (0.0, 0.0),
+            auto_focus: None,
+        }
+    }
+}
+
+fn sample_square() -> Vec3 {
+    Vec3::new(random_real() - 0.5, random_real() - 0.5, 0.0)
+}
+impl Default for Camera {
+    fn default() -> Self {
+        Self {
+            aspect_ratio: 1.0,
+            image_width: 100,
+            v_fov: 90.0,
+            defocus_angle: 0.0,
+            focus_dist: 10.0,
+            look_from: Point3::new(0.0, 0.0, 0.0),
+            look_at: Point3::new(0.0, 0.0, -1.0),
+            v_up: Vec3::new(0.0, 1.0, 0.0),
+            projection: Projection::Perspective,
+            exposure: 1.0,
+            aperture: Aperture::Circular,
+            anamorphic_squeeze: 1.0,
+            lens_shift: (0.0, 0.0),
+            focal_plane_tilt: (0.0, 0.0),
+            auto_focus: None,
+            model: Arc::new(Perspective::default()),
+            image_height: Default::default(),
+            centre: Default::default(),
+            u: Default::default(),
+            v: Default::default(),
+            w: Default::default(),
+            defocus_disk_u: Default::default(),
+            defocus_disk_v: Default::default(),
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use crate::ray::Ray;
+
+use super::{Camera, CameraModel};
+
+/// Parallel projection through a viewport measured in world units, with no depth of field
+#[derive(Clone, Copy, Debug)]
+pub struct Orthographic {
+    half_width: f64,
+    half_height: f64,
+}
+
+impl Orthographic {
+    pub fn new(viewport_height: f64, aspect_ratio: f64) -> Self {
+        Self {
+            half_width: 0.5 * viewport_height * aspect_ratio,
+            half_height: 0.5 * viewport_height,
+        }
+    }
+}
+
+impl CameraModel for Orthographic {
+    fn ray(&self, camera: &Camera, s: f64, t: f64) -> Option<(Ray, f64)> {
+        let origin = camera.centre()
+            + ((2.0 * s - 1.0) * self.half_width) * camera.u()
+            + ((1.0 - 2.0 * t) * self.half_height) * camera.v();
+        Some((Ray::new(origin, -camera.w()), 1.0))
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{
+    ray::Ray,
+    vec3::{dot, Vec3},
+};
+
+use super::{Camera, CameraModel};
+
+/// Thin lens perspective projection, focused on a plane `focus_dist` in front of the camera
+#[derive(Clone, Copy, Debug)]
+pub struct Perspective {
+    half_width: f64,
+    half_height: f64,
+    shift: (f64, f64),
+    /// Focal plane normal in camera space, pointing away from the camera
+    focal_normal: Vec3,
+}
+
+impl Perspective {
+    pub fn new(v_fov: f64, aspect_ratio: f64) -> Self {
+        let half_height = (v_fov.to_radians() / 2.0).tan();
+        Self {
+            half_width: half_height * aspect_ratio,
+            half_height,
+            shift: (0.0, 0.0),
+            focal_normal: Vec3::new(0.0, 0.0, -1.0),
+        }
+    }
+
+    /// Slides the viewport across the image plane by fractions of its width and height,
+    /// positive right and up, without turning the camera
+    pub fn with_shift(mut self, x: f64, y: f64) -> Self {
+        self.shift = (x, y);
+        self
+    }
+
+    /// Tilts the focal plane in degrees about the camera's horizontal axis, with positive
+    /// `tilt` bringing the bottom of the frame into nearer focus, and swings it about the
+    /// vertical axis, with positive `swing` bringing the left nearer
+    pub fn with_tilt(mut self, tilt: f64, swing: f64) -> Self {
+        let (tilt, swing) = (tilt.to_radians(), swing.to_radians());
+        self.focal_normal =
+            Vec3::new(-swing.sin(), -tilt.sin(), -tilt.cos() * swing.cos()).unit_vector();
+        self
+    }
+}
+
+impl Default for Perspective {
+    fn default() -> Self {
+        Self::new(90.0, 1.0)
+    }
+}
+
+impl CameraModel for Perspective {
+    fn ray(&self, camera: &Camera, s: f64, t: f64) -> Option<(Ray, f64)> {
+        // Direction through the pinhole in camera space, at unit distance along the view
+        let d = Vec3::new(
+            (2.0 * s - 1.0 + 2.0 * self.shift.0) * self.half_width,
+            (1.0 - 2.0 * t + 2.0 * self.shift.1) * self.half_height,
+            -1.0,
+        );
+        let to_world = |d: Vec3| d.x() * camera.u() + d.y() * camera.v() + d.z() * camera.w();
+
+        // Distance along d to the focal plane through the point focus_dist ahead
+        let along = dot(&self.focal_normal, &d);
+        if along <= 0.0 {
+            // Parallel to or facing away from the focal plane, so nothing is in focus
+            return Some((Ray::new(camera.centre(), to_world(d)), 1.0));
+        }
+        let focus_point =
+            camera.centre() + to_world(d) * (camera.focus_dist * -self.focal_normal.z() / along);
+
+        let origin = camera.lens_sample();
+        Some((Ray::new(origin, focus_point - origin), 1.0))
+    }
+}
@@ -0,0 +1,79 @@
+use super::CameraOptions;
+
+/// Camera described the way a photographer would set one up, converted into `CameraOptions`
+/// with `options`. World units are taken to be metres
+#[derive(Clone, Copy, Debug)]
+pub struct PhysicalCamera {
+    /// Focal length in mm
+    pub focal_length: f64,
+    /// Aperture f-number, e.g. 2.8 for f/2.8
+    pub f_number: f64,
+    /// Sensor width in mm
+    pub sensor_width: f64,
+    /// Sensor height in mm
+    pub sensor_height: f64,
+    /// Distance to the plane of sharpest focus in metres
+    pub focus_distance: f64,
+    pub iso: f64,
+    /// Shutter speed in seconds
+    pub shutter: f64,
+}
+
+impl PhysicalCamera {
+    /// Vertical field of view in degrees covered by the sensor height
+    pub fn v_fov(&self) -> f64 {
+        (2.0 * (self.sensor_height / (2.0 * self.focal_length)).atan()).to_degrees()
+    }
+
+    /// Angle in degrees the aperture subtends from the focus plane, as used by the thin lens
+    /// `defocus_angle`
+    pub fn defocus_angle(&self) -> f64 {
+        let aperture_radius = self.focal_length / self.f_number / 2.0 / 1000.0;
+        (2.0 * (aperture_radius / self.focus_distance).atan()).to_degrees()
+    }
+
+    /// Exposure value at ISO 100 for the aperture and shutter settings
+    pub fn ev100(&self) -> f64 {
+        (self.f_number * self.f_number / self.shutter * 100.0 / self.iso).log2()
+    }
+
+    /// Linear scale applied to scene radiance before tone mapping. Scene values of 1 are
+    /// treated as direct sunlight, so settings obeying the sunny 16 rule (EV100 of 15) give an
+    /// exposure of 1 and every stop either side doubles or halves it
+    pub fn exposure(&self) -> f64 {
+        (15.0 - self.ev100()).exp2()
+    }
+
+    /// Lens, aperture and exposure settings as camera options for an image `image_width`
+    /// pixels wide, to be completed with the camera's placement using struct update syntax.
+    /// The image height follows the sensor's aspect ratio so the field of view matches the
+    /// sensor both ways, and shouldn't be changed independently
+    pub fn options(&self, image_width: i32) -> CameraOptions {
+        let aspect_ratio = self.sensor_width / self.sensor_height;
+        CameraOptions {
+            aspect_ratio,
+            image_width,
+            image_height: ((image_width as f64 / aspect_ratio).round() as i32).max(1),
+            v_fov: self.v_fov(),
+            defocus_angle: self.defocus_angle(),
+            focus_dist: self.focus_distance,
+            exposure: self.exposure(),
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for PhysicalCamera {
+    /// 50mm lens at f/16 on a full frame sensor, exposed for daylight
+    fn default() -> Self {
+        Self {
+            focal_length: 50.0,
+            f_number: 16.0,
+            sensor_width: 36.0,
+            sensor_height: 24.0,
+            focus_distance: 10.0,
+            iso: 100.0,
+            shutter: 1.0 / 125.0,
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use std::sync::Arc;
+
+use crate::{
+    grid::Region,
+    ray::Ray,
+    util::{degrees_to_radians, PI},
+};
+
+use super::{AutoFocus, Camera, CameraModel, Projection};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Eye {
+    Left,
+    Right,
+}
+
+impl Eye {
+    /// -1 for the left eye and 1 for the right, the direction it's offset along the camera's u
+    fn sign(&self) -> f64 {
+        match self {
+            Eye::Left => -1.0,
+            Eye::Right => 1.0,
+        }
+    }
+}
+
+/// How the two eyes are packed into one output image
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StereoLayout {
+    #[default]
+    SideBySide,
+    /// Left eye above the right, as most VR video players expect for panoramas
+    TopBottom,
+}
+
+/// Pair of cameras either side of a base camera for stereo output
+#[derive(Clone, Copy, Debug)]
+pub struct StereoRig {
+    /// Distance between the eyes in world units
+    pub interocular: f64,
+    /// Distance to the plane where the eyes' views line up, which appears at screen depth
+    pub convergence: f64,
+    pub layout: StereoLayout,
+}
+
+impl StereoRig {
+    pub fn new(interocular: f64, convergence: f64, layout: StereoLayout) -> Self {
+        Self {
+            interocular,
+            convergence,
+            layout,
+        }
+    }
+
+    /// Where `eye` goes in a `width` by `height` output image. With an odd width or height the
+    /// right eye gets the extra column or row, so every pixel is covered
+    pub fn region(&self, eye: Eye, width: usize, height: usize) -> Region {
+        let second = eye == Eye::Right;
+        match self.layout {
+            StereoLayout::SideBySide => {
+                let half = width / 2;
+                if second {
+                    Region::new(half, 0, width - half, height)
+                } else {
+                    Region::new(0, 0, half, height)
+                }
+            }
+            StereoLayout::TopBottom => {
+                let half = height / 2;
+                if second {
+                    Region::new(0, half, width, height - half)
+                } else {
+                    Region::new(0, 0, width, half)
+                }
+            }
+        }
+    }
+
+    /// `base` moved to `eye`'s position and rendering its region of a `width` by `height`
+    /// output image. Perspective eyes stay parallel and use lens shift to converge, which
+    /// avoids the keystoning of toeing them in, while equirectangular cameras become
+    /// omni-directional stereo panoramas
+    pub fn eye_camera(&self, base: &Camera, eye: Eye, width: usize, height: usize) -> Camera {
+        let region = self.region(eye, width, height);
+        let mut options = base.options();
+        options.image_width = region.width as i32;
+        options.image_height = region.height as i32;
+        options.aspect_ratio = region.width as f64 / region.height as f64;
+        if let Some(AutoFocus::Pixel { x, y }) = &mut options.auto_focus {
+            (*x, *y) = self.eye_pixel(eye, *x, *y, width, height);
+        }
+
+        match &options.projection {
+            Projection::Equirectangular => {
+                options.projection = Projection::Custom(Arc::new(OmniStereo::new(
+                    eye,
+                    self.interocular,
+                    self.convergence,
+                )));
+            }
+            projection => {
+                let offset = eye.sign() * self.interocular / 2.0 * base.u();
+                options.look_from += offset;
+                options.look_at += offset;
+
+                if let Projection::Perspective = projection {
+                    // Slide each frustum back towards the middle so they meet at convergence
+                    let half_width =
+                        (degrees_to_radians(options.v_fov) / 2.0).tan() * options.aspect_ratio;
+                    options.lens_shift.0 -=
+                        eye.sign() * self.interocular / 2.0 / self.convergence / (2.0 * half_width);
+                }
+            }
+        }
+        Camera::new(&options)
+    }
+
+    /// Output image pixel (x, y) moved into `eye`'s own image. A pixel in the other eye's half
+    /// is taken to mean the same place in that eye's view, so both eyes focus on one point
+    fn eye_pixel(
+        &self,
+        eye: Eye,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    ) -> (usize, usize) {
+        let (x, y) = (x.min(width - 1), y.min(height - 1));
+        let to = self.region(eye, width, height);
+        let from = [Eye::Left, Eye::Right]
+            .map(|eye| self.region(eye, width, height))
+            .into_iter()
+            .find(|region| region.contains(x, y))
+            .unwrap_or(to);
+
+        let rescale = |p: usize, from_size: usize, to_size: usize| {
+            let p = ((p as f64 + 0.5) / from_size as f64 * to_size as f64) as usize;
+            p.min(to_size.saturating_sub(1))
+        };
+        (
+            rescale(x - from.x, from.width, to.width),
+            rescale(y - from.y, from.height, to.height),
+        )
+    }
+}
+
+/// One eye of an omni-directional stereo panorama. Every direction is seen from a point on a
+/// circle the width of the interocular distance, as if the viewer's head turned to face it
+#[derive(Clone, Copy, Debug)]
+pub struct OmniStereo {
+    eye: Eye,
+    interocular: f64,
+    convergence: f64,
+}
+
+impl OmniStereo {
+    pub fn new(eye: Eye, interocular: f64, convergence: f64) -> Self {
+        Self {
+            eye,
+            interocular,
+            convergence,
+        }
+    }
+}
+
+impl CameraModel for OmniStereo {
+    fn ray(&self, camera: &Camera, s: f64, t: f64) -> Option<(Ray, f64)> {
+        let longitude = (s - 0.5) * 2.0 * PI;
+        let latitude = (0.5 - t) * PI;
+
+        let direction = latitude.cos() * longitude.sin() * camera.u() + latitude.sin() * camera.v()
+            - latitude.cos() * longitude.cos() * camera.w();
+        let right = longitude.cos() * camera.u() + longitude.sin() * camera.w();
+
+        // Shrink the eye circle towards the poles, where there's no consistent left and right
+        let offset = self.eye.sign() * self.interocular / 2.0 * latitude.cos();
+        let origin = camera.centre() + offset * right;
+        let target = camera.centre() + self.convergence * direction;
+        Some((Ray::new(origin, target - origin), 1.0))
+    }
+}
@@ -0,0 +1,12 @@
+use std::fmt::Debug;
+
+use crate::{mod_flat, vec3::Point3};
+
+mod_flat!(noise voxel_grid);
+
+pub trait DensityField: Sync + Send + Debug {
+    /// Density at the world space point `p`
+    fn density(&self, p: &Point3) -> f64;
+    /// Upper bound of `density` over the whole field, used as the tracking majorant
+    fn max_density(&self) -> f64;
+}
@@ -0,0 +1,33 @@
+use crate::{perlin::Perlin, vec3::Point3};
+
+use super::DensityField;
+
+#[derive(Debug, Clone)]
+pub struct NoiseDensity {
+    noise: Perlin,
+    scale: f64,
+    density: f64,
+    octaves: i32,
+}
+
+impl NoiseDensity {
+    pub fn new(scale: f64, density: f64, octaves: i32) -> Self {
+        Self {
+            noise: Perlin::new(),
+            scale,
+            density,
+            octaves: octaves.max(1),
+        }
+    }
+}
+
+impl DensityField for NoiseDensity {
+    fn density(&self, p: &Point3) -> f64 {
+        let turbulence = self.noise.turbulence(&(self.scale * *p), self.octaves);
+        self.density * turbulence.min(1.0)
+    }
+
+    fn max_density(&self) -> f64 {
+        self.density
+    }
+}
@@ -0,0 +1,155 @@
+use std::{fs, path::Path};
+
+use anyhow::{bail, ensure};
+
+use crate::vec3::Point3;
+
+use super::DensityField;
+
+/// Density values sampled on a regular grid spanning the box `min`..`max`, stored with x
+/// varying fastest
+#[derive(Debug, Clone)]
+pub struct VoxelGrid {
+    dims: [usize; 3],
+    min: Point3,
+    max: Point3,
+    data: Vec<f32>,
+    max_density: f64,
+}
+
+impl VoxelGrid {
+    pub fn new(dims: [usize; 3], min: Point3, max: Point3, data: Vec<f32>) -> anyhow::Result<Self> {
+        ensure!(
+            dims.iter().all(|d| *d > 0),
+            "voxel grid dimensions must be non-zero"
+        );
+        let voxels = dims[0]
+            .checked_mul(dims[1])
+            .and_then(|n| n.checked_mul(dims[2]))
+            .ok_or_else(|| anyhow::anyhow!("voxel grid dimensions {dims:?} are too large"))?;
+        ensure!(
+            data.len() == voxels,
+            "expected {voxels} voxels but found {}",
+            data.len()
+        );
+        let max_density = data.iter().fold(0.0_f32, |acc, d| acc.max(*d)) as f64;
+        Ok(Self {
+            dims,
+            min,
+            max,
+            data,
+            max_density,
+        })
+    }
+
+    /// Loads headerless little-endian `f32` voxels
+    pub fn from_raw<P>(
+        filename: P,
+        dims: [usize; 3],
+        min: Point3,
+        max: Point3,
+    ) -> anyhow::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let bytes = fs::read(filename)?;
+        Self::new(dims, min, max, read_f32s(&bytes))
+    }
+
+    /// Loads a single channel float32 Mitsuba style `.vol` file, which stores its own
+    /// dimensions and bounding box
+    pub fn from_vol<P>(filename: P) -> anyhow::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        const HEADER_LEN: usize = 48;
+
+        let bytes = fs::read(filename)?;
+        ensure!(
+            bytes.len() >= HEADER_LEN && &bytes[0..3] == b"VOL",
+            "not a .vol file"
+        );
+        ensure!(bytes[3] == 3, "unsupported .vol version {}", bytes[3]);
+
+        let header = read_i32s(&bytes[4..24]);
+        let (encoding, channels) = (header[0], header[4]);
+        if encoding != 1 || channels != 1 {
+            bail!("only single channel float32 .vol files are supported");
+        }
+        ensure!(
+            header[1..4].iter().all(|d| *d > 0),
+            "invalid .vol dimensions {:?}",
+            &header[1..4]
+        );
+        let dims = [header[1] as usize, header[2] as usize, header[3] as usize];
+
+        let bbox = read_f32s(&bytes[24..HEADER_LEN]);
+        let min = Point3::new(bbox[0] as f64, bbox[1] as f64, bbox[2] as f64);
+        let max = Point3::new(bbox[3] as f64, bbox[4] as f64, bbox[5] as f64);
+
+        Self::new(dims, min, max, read_f32s(&bytes[HEADER_LEN..]))
+    }
+
+    fn voxel(&self, x: usize, y: usize, z: usize) -> f64 {
+        self.data[(z * self.dims[1] + y) * self.dims[0] + x] as f64
+    }
+}
+
+impl DensityField for VoxelGrid {
+    fn density(&self, p: &Point3) -> f64 {
+        let extent = self.max - self.min;
+        let local = [
+            (p.x() - self.min.x()) / extent.x(),
+            (p.y() - self.min.y()) / extent.y(),
+            (p.z() - self.min.z()) / extent.z(),
+        ];
+        if local.iter().any(|c| !(0.0..=1.0).contains(c)) {
+            return 0.0;
+        }
+
+        // Trilinearly interpolate between voxel centres
+        let mut base = [0; 3];
+        let mut frac = [0.0; 3];
+        for axis in 0..3 {
+            let max_index = self.dims[axis] - 1;
+            let coord = (local[axis] * self.dims[axis] as f64 - 0.5).clamp(0.0, max_index as f64);
+            base[axis] = (coord.floor() as usize).min(max_index.saturating_sub(1));
+            frac[axis] = coord - base[axis] as f64;
+        }
+
+        let mut accum = 0.0;
+        for dz in 0..2 {
+            for dy in 0..2 {
+                for dx in 0..2 {
+                    let x = (base[0] + dx).min(self.dims[0] - 1);
+                    let y = (base[1] + dy).min(self.dims[1] - 1);
+                    let z = (base[2] + dz).min(self.dims[2] - 1);
+                    let weight = (if dx == 1 { frac[0] } else { 1.0 - frac[0] })
+                        * (if dy == 1 { frac[1] } else { 1.0 - frac[1] })
+                        * (if dz == 1 { frac[2] } else { 1.0 - frac[2] });
+                    accum += weight * self.voxel(x, y, z);
+                }
+            }
+        }
+
+        accum
+    }
+
+    fn max_density(&self) -> f64 {
+        self.max_density
+    }
+}
+
+fn read_f32s(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+fn read_i32s(bytes: &[u8]) -> Vec<i32> {
+    bytes
+        .chunks_exact(4)
+        .map(|b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, path::Path};
 
 use tracing::Span;
 use tracing_indicatif::{span_ext::IndicatifSpanExt, style::ProgressStyle};
@@ -46,6 +46,99 @@ impl<T, const W: usize, const H: usize> Grid<T, W, H> {
         std::mem::drop(span_header_entered);
         std::mem::drop(span_header);
     }
+
+    /// Like `set_all_parallel` but only for cells inside `region`, leaving the rest as they
+    /// were
+    pub fn set_region_parallel<F>(&mut self, region: &Region, setter: F)
+    where
+        F: Send + Sync + Fn(usize, usize) -> T,
+        T: Send + Debug,
+    {
+        use rayon::prelude::*;
+
+        let region = region.clamped(W, H);
+
+        let span_header = tracing::info_span!("writing region to the grid");
+        span_header.pb_set_style(&ProgressStyle::default_bar());
+        span_header.pb_set_length((region.width * region.height) as u64);
+        let span_header_entered = span_header.enter();
+
+        self.array[region.y..region.y + region.height]
+            .par_iter_mut()
+            .enumerate()
+            .for_each(|(dy, row)| {
+                let y = region.y + dy;
+                for (dx, item) in row[region.x..region.x + region.width]
+                    .iter_mut()
+                    .enumerate()
+                {
+                    *item = setter(region.x + dx, y);
+                    Span::current().pb_inc(1);
+                }
+            });
+
+        std::mem::drop(span_header_entered);
+        std::mem::drop(span_header);
+    }
+}
+
+impl<const W: usize, const H: usize> Grid<[u8; 3], W, H> {
+    /// Loads a previously rendered image, which must be the same size as the grid
+    pub fn from_image<P>(filename: P) -> anyhow::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let image = image::open(filename)?.to_rgb8();
+        if image.width() as usize != W || image.height() as usize != H {
+            anyhow::bail!(
+                "image is {}x{} but the grid is {W}x{H}",
+                image.width(),
+                image.height()
+            );
+        }
+
+        let mut grid: Self = Default::default();
+        for (x, y, pixel) in image.enumerate_pixels() {
+            grid.array[y as usize][x as usize] = pixel.0;
+        }
+        Ok(grid)
+    }
+}
+
+/// Rectangle of grid cells, with (x, y) the top left corner
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Region {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Region {
+    pub fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// The part of the region inside a `width` by `height` grid
+    pub fn clamped(&self, width: usize, height: usize) -> Self {
+        let x = self.x.min(width);
+        let y = self.y.min(height);
+        Self {
+            x,
+            y,
+            width: self.width.min(width - x),
+            height: self.height.min(height - y),
+        }
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        (self.x..self.x + self.width).contains(&x) && (self.y..self.y + self.height).contains(&y)
+    }
 }
 
 impl<T, const W: usize, const H: usize> Default for Grid<T, W, H>
@@ -0,0 +1,185 @@
+use std::sync::Arc;
+
+use crate::{
+    aabb::Aabb,
+    hittable::{axial_hit, azimuth, nearest_hit, pair_intervals, HitInterval, HitRecord, Hittable},
+    material::Material,
+    onb::Onb,
+    ray::Ray,
+    util::Interval,
+    vec3::{Point3, Vec3},
+};
+
+#[derive(Clone, Debug)]
+pub struct Cone {
+    base: Point3,
+    frame: Onb,
+    radius: f64,
+    height: f64,
+    capped: bool,
+    material: Option<Arc<dyn Material>>,
+}
+
+impl Cone {
+    /// Cone with its apex `height` along `axis` from the centre of its base. Uncapped cones
+    /// are open and so can't be used in CSG
+    pub fn new(
+        base: Point3,
+        axis: Vec3,
+        radius: f64,
+        height: f64,
+        capped: bool,
+        material: Option<Arc<dyn Material>>,
+    ) -> Self {
+        Self {
+            base,
+            frame: Onb::new(&axis),
+            radius: radius.max(0.0),
+            height: height.max(1e-8),
+            capped,
+            material,
+        }
+    }
+
+    /// Every intersection along the line of `r`, sorted by `t`
+    fn intersections(&self, r: &Ray) -> Vec<HitRecord> {
+        let o = self.frame.local(&(r.origin() - self.base));
+        let d = self.frame.local(&r.direction());
+        let mut hits = Vec::new();
+
+        // Side wall, x^2 + y^2 = (k * (height - z))^2 in the local frame
+        let k = self.radius / self.height;
+        let k2 = k * k;
+        let oz = self.height - o.z();
+        let a = d.x() * d.x() + d.y() * d.y() - k2 * d.z() * d.z();
+        let h = o.x() * d.x() + o.y() * d.y() + k2 * oz * d.z();
+        let c = o.x() * o.x() + o.y() * o.y() - k2 * oz * oz;
+
+        let roots = if a.abs() < 1e-12 {
+            // Ray parallel to the side wall only crosses it once
+            if h == 0.0 {
+                vec![]
+            } else {
+                vec![-c / (2.0 * h)]
+            }
+        } else {
+            let discriminant = h * h - a * c;
+            if discriminant > 0.0 {
+                let sqrtd = discriminant.sqrt();
+                vec![(-h - sqrtd) / a, (-h + sqrtd) / a]
+            } else if discriminant > -1e-9 * h * h {
+                // A double root, allowing for rounding, which is where lines through the apex
+                // cross it
+                vec![-h / a]
+            } else {
+                vec![]
+            }
+        };
+
+        for t in roots {
+            let p = o + t * d;
+            if (0.0..=self.height).contains(&p.z()) {
+                let normal = Vec3::new(p.x(), p.y(), k2 * (self.height - p.z()));
+                let normal = if normal.near_zero() {
+                    // The normal is undefined exactly at the apex
+                    Vec3::new(0.0, 0.0, 1.0)
+                } else {
+                    normal.unit_vector()
+                };
+                hits.push(axial_hit(
+                    &self.frame,
+                    &self.material,
+                    r,
+                    t,
+                    &normal,
+                    azimuth(&p),
+                    p.z() / self.height,
+                ));
+            }
+        }
+
+        if self.capped && d.z() != 0.0 {
+            let t = -o.z() / d.z();
+            let p = o + t * d;
+            let rho = (p.x() * p.x() + p.y() * p.y()).sqrt();
+            if rho <= self.radius {
+                let normal = Vec3::new(0.0, 0.0, -1.0);
+                hits.push(axial_hit(
+                    &self.frame,
+                    &self.material,
+                    r,
+                    t,
+                    &normal,
+                    azimuth(&p),
+                    rho / self.radius,
+                ));
+            }
+        }
+
+        hits.sort_by(|a, b| a.t.total_cmp(&b.t));
+        hits
+    }
+}
+
+impl Hittable for Cone {
+    fn hit(&self, r: &Ray, ray_t: Interval) -> Option<HitRecord> {
+        nearest_hit(self.intersections(r), r, ray_t)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        Aabb::from_local(
+            Point3::new(-self.radius, -self.radius, 0.0),
+            Point3::new(self.radius, self.radius, self.height),
+            &self.frame,
+            self.base,
+        )
+    }
+
+    fn hit_intervals(&self, r: &Ray) -> Vec<HitInterval> {
+        if !self.capped {
+            return Vec::new();
+        }
+        pair_intervals(self.intersections(r))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cone() -> Cone {
+        Cone::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            1.0,
+            1.0,
+            true,
+            None,
+        )
+    }
+
+    #[test]
+    fn hits_side() {
+        let r = Ray::new(Point3::new(-5.0, 0.5, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        let hit = cone().hit(&r, Interval::new(0.001, f64::INFINITY)).unwrap();
+        assert!((hit.t - 4.5).abs() < 1e-9);
+        let expected = Vec3::new(-1.0, 1.0, 0.0).unit_vector();
+        assert!((hit.normal - expected).near_zero());
+    }
+
+    #[test]
+    fn axial_ray_hits_apex() {
+        let r = Ray::new(Point3::new(0.0, 5.5, 0.0), Vec3::new(0.0, -1.0, 0.0));
+        let hit = cone().hit(&r, Interval::new(0.001, f64::INFINITY)).unwrap();
+        assert!((hit.t - 4.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn axial_ray_intervals_run_from_apex_to_base() {
+        let r = Ray::new(Point3::new(0.0, 5.5, 0.0), Vec3::new(0.0, -1.0, 0.0));
+        let intervals = cone().hit_intervals(&r);
+        assert_eq!(intervals.len(), 1);
+        assert!((intervals[0].enter.t - 4.5).abs() < 1e-9);
+        assert!((intervals[0].exit.t - 5.5).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use crate::{
+    aabb::Aabb,
+    hittable::{HitRecord, Hittable},
+    material::Material,
+    ray::Ray,
+    util::{random_real, Interval, UNIVERSE},
+    vec3::Vec3,
+};
+
+#[derive(Debug)]
+pub struct ConstantMedium {
+    boundary: Box<dyn Hittable>,
+    neg_inv_density: f64,
+    phase_function: Arc<dyn Material>,
+}
+
+impl ConstantMedium {
+    pub fn new(
+        boundary: impl Hittable + 'static,
+        density: f64,
+        phase_function: Arc<dyn Material>,
+    ) -> Self {
+        Self {
+            boundary: Box::new(boundary),
+            neg_inv_density: -1.0 / density,
+            phase_function,
+        }
+    }
+}
+
+impl Hittable for ConstantMedium {
+    fn hit(&self, r: &Ray, ray_t: Interval) -> Option<HitRecord> {
+        let (t_enter, t_exit) = medium_overlap(self.boundary.as_ref(), r, ray_t)?;
+
+        // Sample the free-flight distance and check whether it lands inside the boundary
+        let ray_length = r.direction().length();
+        let distance_inside = (t_exit - t_enter) * ray_length;
+        let hit_distance = self.neg_inv_density * random_real().ln();
+        if hit_distance > distance_inside {
+            return None;
+        }
+
+        let mut hit = HitRecord::default();
+        hit.t = t_enter + hit_distance / ray_length;
+        hit.p = r.at(hit.t);
+
+        // The normal and face are arbitrary inside a volume
+        hit.normal = Vec3::new(1.0, 0.0, 0.0);
+        hit.front_face = true;
+        hit.material = Some(self.phase_function.clone());
+
+        Some(hit)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.boundary.bounding_box()
+    }
+}
+
+/// Parametric range of `r` that lies both inside a medium's `boundary` and within `ray_t`.
+/// The boundary is intersected regardless of `ray_t`, so rays starting inside the medium are
+/// still handled
+pub(crate) fn medium_overlap(
+    boundary: &dyn Hittable,
+    r: &Ray,
+    ray_t: Interval,
+) -> Option<(f64, f64)> {
+    let rec1 = boundary.hit(r, UNIVERSE)?;
+    let rec2 = boundary.hit(r, Interval::new(rec1.t + 0.0001, f64::INFINITY))?;
+
+    let t_enter = rec1.t.max(ray_t.min).max(0.0);
+    let t_exit = rec2.t.min(ray_t.max);
+    (t_enter < t_exit).then_some((t_enter, t_exit))
+}
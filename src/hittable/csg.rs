@@ -0,0 +1,134 @@
+use crate::{
+    aabb::Aabb,
+    hittable::{HitInterval, HitRecord, Hittable},
+    ray::Ray,
+    util::Interval,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CsgOp {
+    Union,
+    Intersection,
+    Difference,
+}
+
+impl CsgOp {
+    fn inside(&self, in_left: bool, in_right: bool) -> bool {
+        match self {
+            CsgOp::Union => in_left || in_right,
+            CsgOp::Intersection => in_left && in_right,
+            CsgOp::Difference => in_left && !in_right,
+        }
+    }
+}
+
+/// Boolean combination of two closed hittables
+#[derive(Debug)]
+pub struct Csg {
+    op: CsgOp,
+    left: Box<dyn Hittable>,
+    right: Box<dyn Hittable>,
+}
+
+impl Csg {
+    pub fn new(op: CsgOp, left: impl Hittable + 'static, right: impl Hittable + 'static) -> Self {
+        Self {
+            op,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    pub fn union(left: impl Hittable + 'static, right: impl Hittable + 'static) -> Self {
+        Self::new(CsgOp::Union, left, right)
+    }
+
+    pub fn intersection(left: impl Hittable + 'static, right: impl Hittable + 'static) -> Self {
+        Self::new(CsgOp::Intersection, left, right)
+    }
+
+    /// Everything inside `left` that is not inside `right`
+    pub fn difference(left: impl Hittable + 'static, right: impl Hittable + 'static) -> Self {
+        Self::new(CsgOp::Difference, left, right)
+    }
+}
+
+impl Hittable for Csg {
+    fn hit(&self, r: &Ray, ray_t: Interval) -> Option<HitRecord> {
+        self.hit_intervals(r)
+            .into_iter()
+            .flat_map(|interval| [interval.enter, interval.exit])
+            .find(|hit| ray_t.surrounds(hit.t) && hit.is_opaque(r))
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        let left = self.left.bounding_box();
+        let right = self.right.bounding_box();
+        match self.op {
+            CsgOp::Union => Aabb::surrounding(&left, &right),
+            CsgOp::Intersection => Aabb::new(
+                Interval::new(left.x.min.max(right.x.min), left.x.max.min(right.x.max)),
+                Interval::new(left.y.min.max(right.y.min), left.y.max.min(right.y.max)),
+                Interval::new(left.z.min.max(right.z.min), left.z.max.min(right.z.max)),
+            ),
+            CsgOp::Difference => left,
+        }
+    }
+
+    fn hit_intervals(&self, r: &Ray) -> Vec<HitInterval> {
+        combine_intervals(
+            self.op,
+            self.left.hit_intervals(r),
+            self.right.hit_intervals(r),
+        )
+    }
+}
+
+/// Merges two sorted interval lists by sweeping over their boundaries in order of `t` and
+/// tracking whether the sweep is inside each side
+pub(crate) fn combine_intervals(
+    op: CsgOp,
+    left: Vec<HitInterval>,
+    right: Vec<HitInterval>,
+) -> Vec<HitInterval> {
+    let mut events: Vec<(HitRecord, bool)> = left
+        .into_iter()
+        .flat_map(|interval| [(interval.enter, true), (interval.exit, true)])
+        .chain(
+            right
+                .into_iter()
+                .flat_map(|interval| [(interval.enter, false), (interval.exit, false)]),
+        )
+        .collect();
+    events.sort_by(|a, b| a.0.t.total_cmp(&b.0.t));
+
+    let mut result = Vec::new();
+    let mut in_left = false;
+    let mut in_right = false;
+    let mut enter: Option<HitRecord> = None;
+
+    for (mut hit, from_left) in events {
+        if from_left {
+            in_left = !in_left;
+        } else {
+            in_right = !in_right;
+        }
+
+        // Surfaces of a subtracted object face inwards on the result
+        if op == CsgOp::Difference && !from_left {
+            hit.front_face = !hit.front_face;
+        }
+
+        let inside = op.inside(in_left, in_right);
+        match enter.take() {
+            None if inside => enter = Some(hit),
+            Some(start) if !inside => result.push(HitInterval {
+                enter: start,
+                exit: hit,
+            }),
+            pending => enter = pending,
+        }
+    }
+
+    result
+}
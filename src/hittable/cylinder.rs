@@ -0,0 +1,202 @@
+use std::sync::Arc;
+
+use crate::{
+    aabb::Aabb,
+    hittable::{nearest_hit, pair_intervals, HitInterval, HitRecord, Hittable},
+    material::Material,
+    onb::Onb,
+    ray::Ray,
+    util::{Interval, PI},
+    vec3::{Point3, Vec3},
+};
+
+#[derive(Clone, Debug)]
+pub struct Cylinder {
+    base: Point3,
+    frame: Onb,
+    radius: f64,
+    height: f64,
+    capped: bool,
+    material: Option<Arc<dyn Material>>,
+}
+
+impl Cylinder {
+    /// Cylinder extending `height` along `axis` from the centre of its base. Uncapped
+    /// cylinders are open tubes and so can't be used in CSG
+    pub fn new(
+        base: Point3,
+        axis: Vec3,
+        radius: f64,
+        height: f64,
+        capped: bool,
+        material: Option<Arc<dyn Material>>,
+    ) -> Self {
+        Self {
+            base,
+            frame: Onb::new(&axis),
+            radius: radius.max(0.0),
+            height: height.max(1e-8),
+            capped,
+            material,
+        }
+    }
+
+    /// Every intersection along the line of `r`, sorted by `t`
+    fn intersections(&self, r: &Ray) -> Vec<HitRecord> {
+        let o = self.frame.local(&(r.origin() - self.base));
+        let d = self.frame.local(&r.direction());
+        let mut hits = Vec::new();
+
+        // Side wall, x^2 + y^2 = radius^2 in the local frame
+        let a = d.x() * d.x() + d.y() * d.y();
+        let h = o.x() * d.x() + o.y() * d.y();
+        let c = o.x() * o.x() + o.y() * o.y() - self.radius * self.radius;
+        let discriminant = h * h - a * c;
+        if a > 0.0 && discriminant > 0.0 {
+            let sqrtd = discriminant.sqrt();
+            for t in [(-h - sqrtd) / a, (-h + sqrtd) / a] {
+                let p = o + t * d;
+                if (0.0..=self.height).contains(&p.z()) {
+                    let normal = Vec3::new(p.x(), p.y(), 0.0) / self.radius;
+                    hits.push(axial_hit(
+                        &self.frame,
+                        &self.material,
+                        r,
+                        t,
+                        &normal,
+                        azimuth(&p),
+                        p.z() / self.height,
+                    ));
+                }
+            }
+        }
+
+        if self.capped && d.z() != 0.0 {
+            for (z, nz) in [(0.0, -1.0), (self.height, 1.0)] {
+                let t = (z - o.z()) / d.z();
+                let p = o + t * d;
+                let rho = (p.x() * p.x() + p.y() * p.y()).sqrt();
+                if rho <= self.radius {
+                    let normal = Vec3::new(0.0, 0.0, nz);
+                    hits.push(axial_hit(
+                        &self.frame,
+                        &self.material,
+                        r,
+                        t,
+                        &normal,
+                        azimuth(&p),
+                        rho / self.radius,
+                    ));
+                }
+            }
+        }
+
+        hits.sort_by(|a, b| a.t.total_cmp(&b.t));
+        hits
+    }
+}
+
+impl Hittable for Cylinder {
+    fn hit(&self, r: &Ray, ray_t: Interval) -> Option<HitRecord> {
+        nearest_hit(self.intersections(r), r, ray_t)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        Aabb::from_local(
+            Point3::new(-self.radius, -self.radius, 0.0),
+            Point3::new(self.radius, self.radius, self.height),
+            &self.frame,
+            self.base,
+        )
+    }
+
+    fn hit_intervals(&self, r: &Ray) -> Vec<HitInterval> {
+        if !self.capped {
+            return Vec::new();
+        }
+        pair_intervals(self.intersections(r))
+    }
+}
+
+/// Hit record for a shape defined in `frame`'s local space, with the tangent running around
+/// the local z axis
+pub(crate) fn axial_hit(
+    frame: &Onb,
+    material: &Option<Arc<dyn Material>>,
+    r: &Ray,
+    t: f64,
+    local_normal: &Vec3,
+    u: f64,
+    v: f64,
+) -> HitRecord {
+    let mut hit = HitRecord::default();
+
+    hit.t = t;
+
+    hit.p = r.at(hit.t);
+    hit.set_face_normal(r, &frame.transform(local_normal));
+    let local_tangent = Vec3::new(-local_normal.y(), local_normal.x(), 0.0);
+    hit.set_tangent_frame(&frame.transform(&local_tangent));
+    (hit.u, hit.v) = (u, v);
+    hit.material = material.clone();
+
+    hit
+}
+
+/// Angle around the local z axis mapped to [0, 1]
+pub(crate) fn azimuth(p: &Point3) -> f64 {
+    (p.y().atan2(p.x()) + PI) / (2.0 * PI)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cylinder() -> Cylinder {
+        Cylinder::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            1.0,
+            2.0,
+            true,
+            None,
+        )
+    }
+
+    #[test]
+    fn hits_side() {
+        let r = Ray::new(Point3::new(-5.0, 1.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        let hit = cylinder()
+            .hit(&r, Interval::new(0.001, f64::INFINITY))
+            .unwrap();
+        assert!((hit.t - 4.0).abs() < 1e-9);
+        assert!((hit.normal - Vec3::new(-1.0, 0.0, 0.0)).near_zero());
+    }
+
+    #[test]
+    fn hits_cap() {
+        let r = Ray::new(Point3::new(0.5, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+        let hit = cylinder()
+            .hit(&r, Interval::new(0.001, f64::INFINITY))
+            .unwrap();
+        assert!((hit.t - 3.0).abs() < 1e-9);
+        assert!((hit.normal - Vec3::new(0.0, 1.0, 0.0)).near_zero());
+    }
+
+    #[test]
+    fn intervals_span_between_caps() {
+        let r = Ray::new(Point3::new(0.5, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+        let intervals = cylinder().hit_intervals(&r);
+        assert_eq!(intervals.len(), 1);
+        assert!((intervals[0].enter.t - 3.0).abs() < 1e-9);
+        assert!((intervals[0].exit.t - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn misses_beyond_height() {
+        let r = Ray::new(Point3::new(-5.0, 3.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        assert!(cylinder()
+            .hit(&r, Interval::new(0.001, f64::INFINITY))
+            .is_none());
+    }
+}
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use crate::{
+    aabb::Aabb,
+    density::DensityField,
+    hittable::{medium_overlap, HitRecord, Hittable},
+    material::Material,
+    ray::Ray,
+    util::{random_real, Interval},
+    vec3::Vec3,
+};
+
+/// Participating medium whose density varies through space, sampled with delta tracking
+/// against the field's majorant
+#[derive(Debug)]
+pub struct HeterogeneousMedium {
+    boundary: Box<dyn Hittable>,
+    density: Arc<dyn DensityField>,
+    density_scale: f64,
+    phase_function: Arc<dyn Material>,
+}
+
+impl HeterogeneousMedium {
+    pub fn new(
+        boundary: impl Hittable + 'static,
+        density: Arc<dyn DensityField>,
+        density_scale: f64,
+        phase_function: Arc<dyn Material>,
+    ) -> Self {
+        Self {
+            boundary: Box::new(boundary),
+            density,
+            density_scale,
+            phase_function,
+        }
+    }
+
+    fn majorant(&self) -> f64 {
+        self.density.max_density() * self.density_scale
+    }
+
+    fn density_at(&self, r: &Ray, t: f64) -> f64 {
+        (self.density.density(&r.at(t)) * self.density_scale).max(0.0)
+    }
+}
+
+impl Hittable for HeterogeneousMedium {
+    fn hit(&self, r: &Ray, ray_t: Interval) -> Option<HitRecord> {
+        let (t_enter, t_exit) = medium_overlap(self.boundary.as_ref(), r, ray_t)?;
+        let majorant = self.majorant();
+        if majorant <= 0.0 {
+            return None;
+        }
+
+        // Delta tracking: take tentative steps through a homogenised medium of density equal
+        // to the majorant, accepting a real collision with probability density / majorant
+        let ray_length = r.direction().length();
+        let mut t = t_enter;
+        loop {
+            t -= (1.0 - random_real()).ln() / (majorant * ray_length);
+            if t >= t_exit {
+                return None;
+            }
+            if random_real() < self.density_at(r, t) / majorant {
+                break;
+            }
+        }
+
+        let mut hit = HitRecord::default();
+        hit.t = t;
+        hit.p = r.at(hit.t);
+
+        // The normal and face are arbitrary inside a volume
+        hit.normal = Vec3::new(1.0, 0.0, 0.0);
+        hit.front_face = true;
+        hit.material = Some(self.phase_function.clone());
+
+        Some(hit)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.boundary.bounding_box()
+    }
+}
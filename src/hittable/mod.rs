@@ -1,20 +1,33 @@
-use std::{fmt::Debug, ops::Neg, sync::Arc};
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    hash::{DefaultHasher, Hash, Hasher},
+    ops::Neg,
+    sync::Arc,
+};
 
 use crate::{
+    aabb::Aabb,
     material::Material,
     mod_flat,
+    onb::Onb,
     ray::Ray,
     util::Interval,
-    vec3::{dot, Point3},
+    vec3::{cross, dot, Point3, Vec3},
 };
 
-mod_flat!(sphere triangle);
+mod_flat!(cone constant_medium csg cylinder heterogeneous_medium sdf_shape sphere subsurface torus triangle);
 
 #[derive(Default, Clone, Debug)]
 pub struct HitRecord {
     pub p: Point3,
     pub normal: Point3,
     pub t: f64,
+    pub u: f64,
+    pub v: f64,
+    /// Unit direction of increasing `u` in the surface, zero if the shape doesn't provide one
+    pub tangent: Vec3,
+    pub bitangent: Vec3,
     pub front_face: bool,
     pub material: Option<Arc<dyn Material>>,
 }
@@ -30,15 +43,127 @@ impl HitRecord {
             outward_normal.neg()
         }
     }
+
+    /// Sets the tangent frame from a direction of increasing u, which is made orthogonal to
+    /// the normal. Must be called after `set_face_normal`, degenerate tangents are ignored
+    pub fn set_tangent_frame(&mut self, tangent: &Vec3) {
+        let tangent = *tangent - dot(tangent, &self.normal) * self.normal;
+        if tangent.near_zero() {
+            return;
+        }
+        self.tangent = tangent.unit_vector();
+        self.bitangent = cross(&self.normal, &self.tangent);
+    }
+
+    /// Whether the surface is solid at this hit along `r` according to its material's opacity.
+    /// Partially transparent surfaces are solid at random in proportion to their opacity, with
+    /// the choice made by hashing the ray and hit so that finding the same hit again, such as a
+    /// medium looking for where the ray leaves it, gives the same answer
+    pub fn is_opaque(&self, r: &Ray) -> bool {
+        match &self.material {
+            Some(material) => {
+                let opacity = material.opacity(self);
+                opacity >= 1.0 || (opacity > 0.0 && hit_hash(r, self.t) < opacity)
+            }
+            None => true,
+        }
+    }
+
+    /// Tangent and bitangent around the normal, making up an arbitrary frame for shapes
+    /// without one
+    pub fn shading_frame(&self) -> (Vec3, Vec3) {
+        if self.tangent.near_zero() {
+            let frame = Onb::new(&self.normal);
+            (frame.u(), frame.v())
+        } else {
+            (self.tangent, self.bitangent)
+        }
+    }
+}
+
+/// Number in [0, 1) that is fixed for a given ray and distance along it. The distance is
+/// rounded to a millimetre-ish world distance so hits found by iterative methods such as
+/// sphere tracing still agree from one search to the next
+fn hit_hash(r: &Ray, t: f64) -> f64 {
+    let mut hasher = DefaultHasher::new();
+    for component in [r.origin(), r.direction()]
+        .iter()
+        .flat_map(|v| [v.x(), v.y(), v.z()])
+    {
+        component.to_bits().hash(&mut hasher);
+    }
+    ((t * r.direction().length() * 1000.0).round() as i64).hash(&mut hasher);
+    (hasher.finish() >> 11) as f64 / (1_u64 << 53) as f64
+}
+
+/// Picks the nearest opaque hit of `hits` inside `ray_t`, for shapes that find every
+/// intersection along the ray up front
+pub(crate) fn nearest_hit(hits: Vec<HitRecord>, r: &Ray, ray_t: Interval) -> Option<HitRecord> {
+    let mut hits: Vec<HitRecord> = hits
+        .into_iter()
+        .filter(|hit| ray_t.surrounds(hit.t))
+        .collect();
+    hits.sort_by(|a, b| a.t.total_cmp(&b.t));
+    hits.into_iter().find(|hit| hit.is_opaque(r))
+}
+
+/// Pairs up the sorted intersections of a closed shape into inside intervals, using which way
+/// each hit faces so stray tangent or apex hits don't throw the pairing out
+pub(crate) fn pair_intervals(hits: Vec<HitRecord>) -> Vec<HitInterval> {
+    let mut intervals = Vec::new();
+    let mut enter = None;
+    for hit in hits {
+        match (enter.take(), hit.front_face) {
+            (None, true) => enter = Some(hit),
+            (Some(enter), false) => intervals.push(HitInterval { enter, exit: hit }),
+            // Entering while already inside, or leaving while outside, so skip this hit
+            (pending, _) => enter = pending,
+        }
+    }
+    intervals
+}
+
+/// Section of a ray between entering and leaving a closed object
+#[derive(Clone, Debug)]
+pub struct HitInterval {
+    pub enter: HitRecord,
+    pub exit: HitRecord,
 }
 
 pub trait Hittable: Sync + Send + Debug {
     fn hit(&self, r: &Ray, ray_t: Interval) -> Option<HitRecord>;
+
+    fn bounding_box(&self) -> Aabb;
+
+    /// Every interval along the whole line of `r` (including negative `t`) that lies inside
+    /// the object, sorted by `t` and non-overlapping. Only closed objects can report these,
+    /// so the default is empty
+    fn hit_intervals(&self, #[allow(unused_variables)] r: &Ray) -> Vec<HitInterval> {
+        Vec::new()
+    }
+}
+
+/// Lets one object be shared between several others, such as the boundary of a volume that is
+/// also rendered as a surface
+impl<T: Hittable + ?Sized> Hittable for Arc<T> {
+    fn hit(&self, r: &Ray, ray_t: Interval) -> Option<HitRecord> {
+        self.as_ref().hit(r, ray_t)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.as_ref().bounding_box()
+    }
+
+    fn hit_intervals(&self, r: &Ray) -> Vec<HitInterval> {
+        self.as_ref().hit_intervals(r)
+    }
 }
 
 #[derive(Default, Debug)]
 pub struct HittableList {
     pub objects: Vec<Box<dyn Hittable>>,
+    /// Objects added with a name, shared with `objects` so they're still found if it's changed
+    names: HashMap<String, Arc<dyn Hittable>>,
 }
 
 impl HittableList {
@@ -50,8 +175,20 @@ impl HittableList {
         self.objects.push(Box::new(object));
     }
 
+    /// Adds an object that can be looked up again by `name`, such as to focus the camera on it
+    pub fn add_named(&mut self, name: impl Into<String>, object: impl Hittable + 'static) {
+        let object: Arc<dyn Hittable> = Arc::new(object);
+        self.names.insert(name.into(), object.clone());
+        self.add(object);
+    }
+
+    pub fn named(&self, name: &str) -> Option<&dyn Hittable> {
+        self.names.get(name).map(|object| object.as_ref())
+    }
+
     pub fn clear(&mut self) {
         self.objects.clear();
+        self.names.clear();
     }
 }
 
@@ -69,4 +206,16 @@ impl Hittable for HittableList {
 
         result
     }
+
+    fn bounding_box(&self) -> Aabb {
+        self.objects.iter().fold(Aabb::default(), |bbox, object| {
+            Aabb::surrounding(&bbox, &object.bounding_box())
+        })
+    }
+
+    fn hit_intervals(&self, r: &Ray) -> Vec<HitInterval> {
+        self.objects.iter().fold(Vec::new(), |acc, object| {
+            combine_intervals(CsgOp::Union, acc, object.hit_intervals(r))
+        })
+    }
 }
@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use crate::{
+    aabb::Aabb,
+    hittable::{HitRecord, Hittable},
+    material::Material,
+    ray::Ray,
+    sdf::Sdf,
+    util::Interval,
+    vec3::Vec3,
+};
+
+const MAX_STEPS: usize = 256;
+const EPSILON: f64 = 1e-4;
+
+/// Surface defined by a signed distance function, intersected by sphere tracing inside
+/// `bounds`
+#[derive(Debug)]
+pub struct SdfShape {
+    sdf: Box<dyn Sdf>,
+    bounds: Aabb,
+    step_scale: f64,
+    material: Option<Arc<dyn Material>>,
+}
+
+impl SdfShape {
+    pub fn new(sdf: impl Sdf + 'static, bounds: Aabb, material: Option<Arc<dyn Material>>) -> Self {
+        Self {
+            sdf: Box::new(sdf),
+            bounds,
+            step_scale: 1.0,
+            material,
+        }
+    }
+
+    /// Shortens every step by `step_scale`, for distance fields that overestimate such as
+    /// twisted shapes
+    pub fn with_step_scale(mut self, step_scale: f64) -> Self {
+        self.step_scale = step_scale.clamp(0.01, 1.0);
+        self
+    }
+
+    fn hit_at(&self, r: &Ray, t: f64) -> HitRecord {
+        let mut hit = HitRecord::default();
+
+        hit.t = t;
+
+        hit.p = r.at(hit.t);
+        let outward_normal = self.normal_at(&hit.p);
+        hit.set_face_normal(r, &outward_normal);
+        hit.material = self.material.clone();
+
+        hit
+    }
+
+    fn normal_at(&self, p: &Vec3) -> Vec3 {
+        // Central differences of the distance field
+        let h = EPSILON;
+        let dx = Vec3::new(h, 0.0, 0.0);
+        let dy = Vec3::new(0.0, h, 0.0);
+        let dz = Vec3::new(0.0, 0.0, h);
+        Vec3::new(
+            self.sdf.distance(&(*p + dx)) - self.sdf.distance(&(*p - dx)),
+            self.sdf.distance(&(*p + dy)) - self.sdf.distance(&(*p - dy)),
+            self.sdf.distance(&(*p + dz)) - self.sdf.distance(&(*p - dz)),
+        )
+        .unit_vector()
+    }
+}
+
+impl Hittable for SdfShape {
+    fn hit(&self, r: &Ray, ray_t: Interval) -> Option<HitRecord> {
+        let span = self.bounds.hit(r, ray_t)?;
+        let ray_length = r.direction().length();
+
+        // March in world distance, converting back to ray parameter at each step. Rays starting
+        // inside the surface march on the absolute distance until they leave it
+        let mut t = span.min;
+        // Which side of the surface the ray is on, unknown until the march is clear of the
+        // EPSILON shell so that rays leaving the surface don't immediately hit it again
+        let mut inside = None;
+        for _ in 0..MAX_STEPS {
+            let distance = self.sdf.distance(&r.at(t));
+            match inside {
+                None if distance.abs() >= EPSILON => inside = Some(distance < 0.0),
+                // Overestimating fields can step straight through the surface, so a change of
+                // side also counts as a hit
+                Some(side) if distance.abs() < EPSILON || (distance < 0.0) != side => {
+                    let hit = self.hit_at(r, t);
+                    if hit.is_opaque(r) {
+                        return Some(hit);
+                    }
+                    // Carry on past a transparent surface, from just beyond it
+                    t += 2.0 * EPSILON / ray_length;
+                    inside = None;
+                    continue;
+                }
+                _ => {}
+            }
+            t += (distance.abs() * self.step_scale).max(EPSILON * 0.5) / ray_length;
+            if t > span.max {
+                return None;
+            }
+        }
+
+        None
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bounds
+    }
+}
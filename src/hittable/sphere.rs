@@ -1,9 +1,11 @@
 use std::sync::Arc;
 
 use crate::{
-    hittable::{HitRecord, Hittable},
+    aabb::Aabb,
+    hittable::{HitInterval, HitRecord, Hittable},
     material::Material,
-    util::Interval,
+    ray::Ray,
+    util::{Interval, PI},
     vec3::{dot, Point3, Vec3},
 };
 
@@ -22,10 +24,25 @@ impl Sphere {
             material,
         }
     }
+
+    fn hit_at(&self, r: &Ray, t: f64) -> HitRecord {
+        let mut hit = HitRecord::default();
+
+        hit.t = t;
+
+        hit.p = r.at(hit.t);
+        let outward_normal = (hit.p - self.centre) / self.radius;
+        hit.set_face_normal(r, &outward_normal);
+        hit.set_tangent_frame(&Vec3::new(outward_normal.z(), 0.0, -outward_normal.x()));
+        (hit.u, hit.v) = sphere_uv(&outward_normal);
+        hit.material = self.material.clone();
+
+        hit
+    }
 }
 
 impl Hittable for Sphere {
-    fn hit(&self, r: &crate::ray::Ray, ray_t: Interval) -> Option<HitRecord> {
+    fn hit(&self, r: &Ray, ray_t: Interval) -> Option<HitRecord> {
         let oc: Vec3 = self.centre - r.origin();
         let a = r.direction().length_squared();
         let h = dot(&r.direction(), &oc);
@@ -38,24 +55,43 @@ impl Hittable for Sphere {
 
         let sqrtd = discriminant.sqrt();
 
-        // Find the nearest root that lies in the acceptable range
-        let mut root = (h - sqrtd) / a;
-        if !ray_t.surrounds(root) {
-            root = (h + sqrtd) / a;
-            if !ray_t.surrounds(root) {
-                return None;
-            }
-        }
+        // Find the nearest root that lies in the acceptable range, skipping any that land on a
+        // transparent part of the surface
+        [(h - sqrtd) / a, (h + sqrtd) / a]
+            .into_iter()
+            .filter(|root| ray_t.surrounds(*root))
+            .map(|root| self.hit_at(r, root))
+            .find(|hit| hit.is_opaque(r))
+    }
 
-        let mut hit = HitRecord::default();
+    fn bounding_box(&self) -> Aabb {
+        let rvec = Vec3::new(self.radius, self.radius, self.radius);
+        Aabb::from_points(&[self.centre - rvec, self.centre + rvec])
+    }
 
-        hit.t = root;
+    fn hit_intervals(&self, r: &Ray) -> Vec<HitInterval> {
+        let oc: Vec3 = self.centre - r.origin();
+        let a = r.direction().length_squared();
+        let h = dot(&r.direction(), &oc);
+        let c = oc.length_squared() - self.radius * self.radius;
 
-        hit.p = r.at(hit.t);
-        let outward_normal = (hit.p - self.centre) / self.radius;
-        hit.set_face_normal(r, &outward_normal);
-        hit.material = self.material.clone();
+        let discriminant = h * h - a * c;
+        if discriminant <= 0.0 {
+            return Vec::new();
+        }
 
-        Some(hit)
+        let sqrtd = discriminant.sqrt();
+        vec![HitInterval {
+            enter: self.hit_at(r, (h - sqrtd) / a),
+            exit: self.hit_at(r, (h + sqrtd) / a),
+        }]
     }
 }
+
+/// Maps a point on the unit sphere to texture coordinates, with `v` running from the south to
+/// the north pole and `u` around the y axis starting from -x
+fn sphere_uv(p: &Point3) -> (f64, f64) {
+    let theta = (-p.y()).clamp(-1.0, 1.0).acos();
+    let phi = (-p.z()).atan2(p.x()) + PI;
+    (phi / (2.0 * PI), theta / PI)
+}
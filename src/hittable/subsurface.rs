@@ -0,0 +1,161 @@
+use std::sync::Arc;
+
+use crate::{
+    aabb::Aabb,
+    hittable::{medium_overlap, HitRecord, Hittable},
+    material::{Dielectric, HenyeyGreenstein, Material},
+    ray::Ray,
+    util::{random_real, Interval},
+    vec3::{Colour, Vec3},
+};
+
+/// Translucent object such as skin or wax. Light refracts through a dielectric surface then
+/// random walks through a scattering medium filling the closed `boundary` until it leaves.
+/// The boundary's own material is ignored
+#[derive(Debug)]
+pub struct Subsurface {
+    boundary: Arc<dyn Hittable>,
+    surface: Arc<dyn Material>,
+    lobe: Arc<dyn Material>,
+    scattering: Colour,
+    extinction: Colour,
+    majorant: f64,
+}
+
+impl Subsurface {
+    /// Coefficients are per unit distance for each channel, `g` is the Henyey-Greenstein
+    /// anisotropy of the scattering
+    pub fn new(
+        boundary: Arc<dyn Hittable>,
+        refraction_index: f64,
+        scattering: Colour,
+        absorption: Colour,
+        g: f64,
+    ) -> Self {
+        let extinction = scattering + absorption;
+        Self {
+            boundary,
+            surface: Arc::new(Dielectric::new(refraction_index)),
+            lobe: Arc::new(HenyeyGreenstein::new(Colour::new(1.0, 1.0, 1.0), g)),
+            scattering,
+            extinction,
+            majorant: extinction.r().max(extinction.g()).max(extinction.b()),
+        }
+    }
+
+    /// Builds the coefficients from the single scattering `albedo`, clamped to [0, 1] in each
+    /// channel, and the average distance light travels between collisions
+    pub fn from_mean_free_path(
+        boundary: Arc<dyn Hittable>,
+        refraction_index: f64,
+        albedo: Colour,
+        mean_free_path: Colour,
+        g: f64,
+    ) -> Self {
+        let albedo = Colour::new(
+            albedo.r().clamp(0.0, 1.0),
+            albedo.g().clamp(0.0, 1.0),
+            albedo.b().clamp(0.0, 1.0),
+        );
+        let extinction = Colour::new(
+            1.0 / mean_free_path.r().max(1e-8),
+            1.0 / mean_free_path.g().max(1e-8),
+            1.0 / mean_free_path.b().max(1e-8),
+        );
+        let scattering = albedo * extinction;
+        Self::new(
+            boundary,
+            refraction_index,
+            scattering,
+            extinction - scattering,
+            g,
+        )
+    }
+
+    /// Delta tracks through the medium between `t_enter` and `t_exit` against the majorant.
+    /// Each tentative collision is real with the average probability across the channels and
+    /// null otherwise, so the weight makes up for the difference in each channel. Returns the
+    /// first real collision, or the weight to carry on with if the ray gets through
+    fn track(&self, r: &Ray, t_enter: f64, t_exit: f64) -> Result<HitRecord, Colour> {
+        let extinction = self.extinction / self.majorant;
+        let p_real = (extinction.r() + extinction.g() + extinction.b()) / 3.0;
+        let null = Colour::new(1.0, 1.0, 1.0) - extinction;
+
+        let ray_length = r.direction().length();
+        let mut weight = Colour::new(1.0, 1.0, 1.0);
+        let mut t = t_enter;
+        loop {
+            t -= (1.0 - random_real()).ln() / (self.majorant * ray_length);
+            if t >= t_exit {
+                return Err(weight);
+            }
+            if random_real() < p_real {
+                break;
+            }
+            weight = weight * null / (1.0 - p_real);
+        }
+
+        let mut hit = HitRecord::default();
+        hit.t = t;
+        hit.p = r.at(hit.t);
+
+        // The normal and face are arbitrary inside a volume
+        hit.normal = Vec3::new(1.0, 0.0, 0.0);
+        hit.front_face = true;
+        hit.material = Some(Arc::new(Weighted {
+            inner: self.lobe.clone(),
+            weight: weight * self.scattering / (self.majorant * p_real),
+        }));
+
+        Ok(hit)
+    }
+}
+
+impl Hittable for Subsurface {
+    fn hit(&self, r: &Ray, ray_t: Interval) -> Option<HitRecord> {
+        let surface = self.boundary.hit(r, ray_t);
+        let max = surface.as_ref().map_or(ray_t.max, |hit| hit.t);
+
+        // A collision inside the medium only counts if it comes before the surface
+        let mut weight = None;
+        if self.majorant > 0.0 {
+            if let Some((t_enter, t_exit)) =
+                medium_overlap(self.boundary.as_ref(), r, Interval::new(ray_t.min, max))
+            {
+                match self.track(r, t_enter, t_exit) {
+                    Ok(hit) => return Some(hit),
+                    Err(through) => weight = Some(through),
+                }
+            }
+        }
+
+        surface.map(|mut hit| {
+            hit.material = Some(match weight {
+                Some(weight) => Arc::new(Weighted {
+                    inner: self.surface.clone(),
+                    weight,
+                }),
+                None => self.surface.clone(),
+            });
+            hit
+        })
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.boundary.bounding_box()
+    }
+}
+
+/// Scales whatever `inner` scatters by the weight delta tracking built up on the way to a hit
+#[derive(Debug)]
+struct Weighted {
+    inner: Arc<dyn Material>,
+    weight: Colour,
+}
+
+impl Material for Weighted {
+    fn scatter(&self, ray_in: &Ray, hit_rec: &HitRecord) -> Option<(Ray, Colour)> {
+        let (scattered, attenuation) = self.inner.scatter(ray_in, hit_rec)?;
+        Some((scattered, self.weight * attenuation))
+    }
+}
@@ -0,0 +1,164 @@
+use std::sync::Arc;
+
+use crate::{
+    aabb::Aabb,
+    hittable::{azimuth, nearest_hit, pair_intervals, HitInterval, HitRecord, Hittable},
+    material::Material,
+    onb::Onb,
+    polynomial::solve_quartic,
+    ray::Ray,
+    util::{Interval, PI, UNIVERSE},
+    vec3::{dot, Point3, Vec3},
+};
+
+#[derive(Clone, Debug)]
+pub struct Torus {
+    centre: Point3,
+    frame: Onb,
+    major_radius: f64,
+    minor_radius: f64,
+    bbox: Aabb,
+    material: Option<Arc<dyn Material>>,
+}
+
+impl Torus {
+    /// Torus lying in the plane perpendicular to `axis`, with a tube of `minor_radius` swept
+    /// around a circle of `major_radius`
+    pub fn new(
+        centre: Point3,
+        axis: Vec3,
+        major_radius: f64,
+        minor_radius: f64,
+        material: Option<Arc<dyn Material>>,
+    ) -> Self {
+        let frame = Onb::new(&axis);
+        let major_radius = major_radius.max(0.0);
+        let minor_radius = minor_radius.max(0.0);
+        let extent = major_radius + minor_radius;
+        let bbox = Aabb::from_local(
+            Point3::new(-extent, -extent, -minor_radius),
+            Point3::new(extent, extent, minor_radius),
+            &frame,
+            centre,
+        );
+        Self {
+            centre,
+            frame,
+            major_radius,
+            minor_radius,
+            bbox,
+            material,
+        }
+    }
+
+    /// Every intersection along the line of `r`, sorted by `t`
+    fn intersections(&self, r: &Ray) -> Vec<HitRecord> {
+        // Skip the quartic for rays that can't reach the torus at all
+        if self.bbox.hit(r, UNIVERSE).is_none() {
+            return Vec::new();
+        }
+
+        let o = self.frame.local(&(r.origin() - self.centre));
+        let d = self.frame.local(&r.direction());
+
+        // Solve in terms of distance along the normalised direction to keep the coefficients
+        // well scaled
+        let length = d.length();
+        let d = d / length;
+
+        let r2 = self.major_radius * self.major_radius;
+        let four_r2 = 4.0 * r2;
+        let f = dot(&o, &d);
+        let g = o.length_squared() + r2 - self.minor_radius * self.minor_radius;
+
+        let roots = solve_quartic(
+            1.0,
+            4.0 * f,
+            4.0 * f * f + 2.0 * g - four_r2 * (1.0 - d.z() * d.z()),
+            4.0 * f * g - 2.0 * four_r2 * (f - o.z() * d.z()),
+            g * g - four_r2 * (o.length_squared() - o.z() * o.z()),
+        );
+
+        roots
+            .into_iter()
+            .map(|s| {
+                let p = o + s * d;
+                self.hit_at(r, s / length, &p)
+            })
+            .collect()
+    }
+
+    fn hit_at(&self, r: &Ray, t: f64, local_p: &Point3) -> HitRecord {
+        let mut hit = HitRecord::default();
+
+        hit.t = t;
+
+        hit.p = r.at(hit.t);
+
+        // The normal points away from the nearest point on the circle through the tube
+        let rho = (local_p.x() * local_p.x() + local_p.y() * local_p.y()).sqrt();
+        let ring = if rho > 0.0 {
+            Vec3::new(local_p.x(), local_p.y(), 0.0) * (self.major_radius / rho)
+        } else {
+            Vec3::new(self.major_radius, 0.0, 0.0)
+        };
+        let local_normal = (*local_p - ring).unit_vector();
+        hit.set_face_normal(r, &self.frame.transform(&local_normal));
+        let local_tangent = Vec3::new(-local_p.y(), local_p.x(), 0.0);
+        hit.set_tangent_frame(&self.frame.transform(&local_tangent));
+
+        hit.u = azimuth(local_p);
+        hit.v = (local_p.z().atan2(rho - self.major_radius) + PI) / (2.0 * PI);
+        hit.material = self.material.clone();
+
+        hit
+    }
+}
+
+impl Hittable for Torus {
+    fn hit(&self, r: &Ray, ray_t: Interval) -> Option<HitRecord> {
+        nearest_hit(self.intersections(r), r, ray_t)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bbox
+    }
+
+    fn hit_intervals(&self, r: &Ray) -> Vec<HitInterval> {
+        pair_intervals(self.intersections(r))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ray_through_centre_crosses_both_sides_of_the_tube() {
+        let torus = Torus::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            2.0,
+            0.5,
+            None,
+        );
+        let r = Ray::new(Point3::new(-5.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+
+        let hit = torus.hit(&r, Interval::new(0.001, f64::INFINITY)).unwrap();
+        assert!((hit.t - 2.5).abs() < 1e-6);
+        assert!((hit.normal - Vec3::new(-1.0, 0.0, 0.0)).near_zero());
+
+        let intervals: Vec<_> = torus
+            .hit_intervals(&r)
+            .iter()
+            .map(|i| (i.enter.t, i.exit.t))
+            .collect();
+        assert_eq!(intervals.len(), 2);
+        for ((enter, exit), (expected_enter, expected_exit)) in
+            intervals.into_iter().zip([(2.5, 3.5), (6.5, 7.5)])
+        {
+            assert!((enter - expected_enter).abs() < 1e-6);
+            assert!((exit - expected_exit).abs() < 1e-6);
+        }
+    }
+}
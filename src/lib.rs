@@ -1,11 +1,19 @@
+pub mod aabb;
 pub mod camera;
+pub mod density;
 pub mod grid;
 pub mod hittable;
 pub mod macros;
 pub mod material;
 pub mod obj;
+pub mod onb;
+pub mod perlin;
+pub mod polynomial;
 pub mod ray;
 pub mod renderer;
+pub mod sdf;
+pub mod spectrum;
 pub mod state;
+pub mod texture;
 pub mod util;
 pub mod vec3;
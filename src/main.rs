@@ -134,6 +134,7 @@ fn software_render() {
         v_up: Vec3::new(0.0, 1.0, 0.0),
         defocus_angle: 0.6,
         focus_dist: 10.0,
+        ..Default::default()
     });
 
     let pixels: Grid<[u8; 3], WIDTH, HEIGHT> = Default::default();
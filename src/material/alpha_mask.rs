@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use crate::{hittable::HitRecord, ray::Ray, texture::Texture, vec3::Colour};
+
+use super::Material;
+
+/// Cuts holes in another material using an opacity texture, for foliage and decals
+#[derive(Debug, Clone)]
+pub struct AlphaMask {
+    inner: Arc<dyn Material>,
+    opacity: Arc<dyn Texture>,
+}
+
+impl AlphaMask {
+    pub fn new(inner: Arc<dyn Material>, opacity: Arc<dyn Texture>) -> Self {
+        Self { inner, opacity }
+    }
+}
+
+impl Material for AlphaMask {
+    fn scatter(&self, ray_in: &Ray, hit_rec: &HitRecord) -> Option<(Ray, Colour)> {
+        self.inner.scatter(ray_in, hit_rec)
+    }
+
+    fn opacity(&self, hit_rec: &HitRecord) -> f64 {
+        let opacity = self.opacity.scalar(hit_rec.u, hit_rec.v, &hit_rec.p);
+        opacity.clamp(0.0, 1.0) * self.inner.opacity(hit_rec)
+    }
+}
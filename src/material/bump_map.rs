@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use crate::{hittable::HitRecord, ray::Ray, texture::Texture, vec3::Colour};
+
+use super::{scatter_with_shading_normal, Material};
+
+/// Step in texture space used to take derivatives of the height map
+const DELTA: f64 = 1e-3;
+
+/// Perturbs the shading normal of another material using the slope of a height texture
+#[derive(Debug, Clone)]
+pub struct BumpMap {
+    inner: Arc<dyn Material>,
+    height: Arc<dyn Texture>,
+    scale: f64,
+}
+
+impl BumpMap {
+    pub fn new(inner: Arc<dyn Material>, height: Arc<dyn Texture>, scale: f64) -> Self {
+        Self {
+            inner,
+            height,
+            scale,
+        }
+    }
+}
+
+impl Material for BumpMap {
+    fn scatter(&self, ray_in: &Ray, hit_rec: &HitRecord) -> Option<(Ray, Colour)> {
+        let (u, v, p) = (hit_rec.u, hit_rec.v, &hit_rec.p);
+        let height = self.height.scalar(u, v, p);
+        let du = (self.height.scalar(u + DELTA, v, p) - height) / DELTA;
+        let dv = (self.height.scalar(u, v + DELTA, p) - height) / DELTA;
+
+        // Tilt the normal against the slope in each tangent direction
+        let (tangent, bitangent) = hit_rec.shading_frame();
+        let shading_normal = hit_rec.normal - self.scale * (du * tangent + dv * bitangent);
+
+        scatter_with_shading_normal(self.inner.as_ref(), ray_in, hit_rec, &shading_normal)
+    }
+
+    fn opacity(&self, hit_rec: &HitRecord) -> f64 {
+        self.inner.opacity(hit_rec)
+    }
+}
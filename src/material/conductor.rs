@@ -0,0 +1,79 @@
+use crate::{
+    hittable::HitRecord,
+    onb::Onb,
+    ray::Ray,
+    vec3::{dot, Colour, Vec3},
+};
+
+use super::{fresnel_conductor, Material, TrowbridgeReitz};
+
+/// Physically based metal using a GGX microfacet distribution and the Fresnel equations for a
+/// complex index of refraction
+#[derive(Debug, Clone)]
+pub struct Conductor {
+    eta: Colour,
+    k: Colour,
+    distribution: TrowbridgeReitz,
+}
+
+impl Conductor {
+    pub fn new(eta: Colour, k: Colour, roughness: f64) -> Self {
+        Self {
+            eta,
+            k,
+            distribution: TrowbridgeReitz::from_roughness(roughness),
+        }
+    }
+
+    pub fn gold(roughness: f64) -> Self {
+        Self::new(
+            Colour::new(0.143, 0.374, 1.442),
+            Colour::new(3.983, 2.385, 1.603),
+            roughness,
+        )
+    }
+
+    pub fn copper(roughness: f64) -> Self {
+        Self::new(
+            Colour::new(0.200, 0.924, 1.102),
+            Colour::new(3.912, 2.452, 2.142),
+            roughness,
+        )
+    }
+
+    pub fn aluminium(roughness: f64) -> Self {
+        Self::new(
+            Colour::new(1.657, 0.880, 0.521),
+            Colour::new(9.224, 6.270, 4.837),
+            roughness,
+        )
+    }
+}
+
+impl Material for Conductor {
+    fn scatter(&self, ray_in: &Ray, hit_rec: &HitRecord) -> Option<(Ray, Colour)> {
+        let frame = Onb::new(&hit_rec.normal);
+        let wo = frame.local(&-ray_in.direction().unit_vector());
+        if wo.z() <= 0.0 {
+            return None;
+        }
+
+        if self.distribution.is_smooth() {
+            let wi = Vec3::new(-wo.x(), -wo.y(), wo.z());
+            let attenuation = fresnel_conductor(wo.z(), &self.eta, &self.k);
+            return Some((Ray::new(hit_rec.p, frame.transform(&wi)), attenuation));
+        }
+
+        // Reflect about a sampled visible microfacet normal. Directions that end up below the
+        // surface would need multiple scattering between microfacets, which isn't modelled
+        let wm = self.distribution.sample_wm(&wo);
+        let wi = -wo + 2.0 * dot(&wo, &wm) * wm;
+        if wi.z() <= 0.0 {
+            return None;
+        }
+
+        let attenuation = fresnel_conductor(dot(&wo, &wm), &self.eta, &self.k)
+            * self.distribution.sampled_weight(&wo, &wi);
+        Some((Ray::new(hit_rec.p, frame.transform(&wi)), attenuation))
+    }
+}
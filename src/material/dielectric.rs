@@ -7,17 +7,85 @@ use crate::{
     vec3::{dot, Colour},
 };
 
-use super::Material;
+use super::{Material, ThinFilm};
+
+/// Wavelength dependence of the refractive index, only used when rendering spectrally
+#[derive(Debug, Clone, Copy)]
+pub enum Dispersion {
+    None,
+    /// `n = a + b / lambda^2` with lambda in micrometres
+    Cauchy {
+        a: f64,
+        b: f64,
+    },
+    /// `n^2 = 1 + sum(b_i lambda^2 / (lambda^2 - c_i))` with lambda in micrometres
+    Sellmeier {
+        b: [f64; 3],
+        c: [f64; 3],
+    },
+}
+
+impl Dispersion {
+    pub fn refraction_index(&self, wavelength: f64) -> Option<f64> {
+        let micrometres = wavelength / 1000.0;
+        let l2 = micrometres * micrometres;
+        match self {
+            Dispersion::None => None,
+            Dispersion::Cauchy { a, b } => Some(a + b / l2),
+            Dispersion::Sellmeier { b, c } => {
+                let n2 = 1.0 + b.iter().zip(c).map(|(b, c)| b * l2 / (l2 - c)).sum::<f64>();
+                Some(n2.max(1.0).sqrt())
+            }
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct Dielectric {
     refraction_index: f64,
+    absorption: Colour,
+    dispersion: Dispersion,
+    thin_film: Option<ThinFilm>,
 }
 
 impl Dielectric {
     pub fn new(refraction_index: f64) -> Self {
-        Self { refraction_index }
+        Self {
+            refraction_index,
+            absorption: Colour::new(0.0, 0.0, 0.0),
+            dispersion: Dispersion::None,
+            thin_film: None,
+        }
+    }
+
+    /// Schott N-BK7 crown glass
+    pub fn bk7() -> Self {
+        Self::new(1.5168).with_dispersion(Dispersion::Sellmeier {
+            b: [1.03961212, 0.231792344, 1.01046945],
+            c: [0.00600069867, 0.0200179144, 103.560653],
+        })
+    }
+
+    /// Replaces the fixed refractive index with a wavelength dependent one for rays that carry
+    /// a wavelength
+    pub fn with_dispersion(mut self, dispersion: Dispersion) -> Self {
+        self.dispersion = dispersion;
+        self
     }
+
+    /// Absorption coefficient per unit distance for each channel, following the Beer-Lambert
+    /// law so thicker sections of glass are more strongly tinted
+    pub fn with_absorption(mut self, absorption: Colour) -> Self {
+        self.absorption = absorption;
+        self
+    }
+
+    /// Coats the outside of the surface with a thin film, `thickness` given in nanometres
+    pub fn with_thin_film(mut self, thickness: f64, refraction_index: f64) -> Self {
+        self.thin_film = Some(ThinFilm::new(thickness, refraction_index));
+        self
+    }
+
     fn reflectance(&self, cosine: f64, refraction_index: f64) -> f64 {
         let mut r0 = (1.0 - refraction_index) / (1.0 + refraction_index);
         r0 = r0 * r0;
@@ -27,11 +95,25 @@ impl Dielectric {
 
 impl Material for Dielectric {
     fn scatter(&self, ray_in: &Ray, hit_rec: &HitRecord) -> Option<(Ray, Colour)> {
-        let attenuation = Colour::new(1.0, 1.0, 1.0);
+        // Hitting the back face means the ray has just travelled through the medium
+        let attenuation = if hit_rec.front_face {
+            Colour::new(1.0, 1.0, 1.0)
+        } else {
+            let distance = hit_rec.t * ray_in.direction().length();
+            Colour::new(
+                (-self.absorption.r() * distance).exp(),
+                (-self.absorption.g() * distance).exp(),
+                (-self.absorption.b() * distance).exp(),
+            )
+        };
+        let refraction_index = ray_in
+            .wavelength()
+            .and_then(|wavelength| self.dispersion.refraction_index(wavelength))
+            .unwrap_or(self.refraction_index);
         let ri = if hit_rec.front_face {
-            1.0 / self.refraction_index
+            1.0 / refraction_index
         } else {
-            self.refraction_index
+            refraction_index
         };
 
         let unit_direction = ray_in.direction().unit_vector();
@@ -41,11 +123,36 @@ impl Material for Dielectric {
 
         let cannot_refract = ri * sin_theta > 1.0;
 
-        // || self.reflectance(cos_theta, ri) > random_real()
-        let direction = if cannot_refract || self.reflectance(cos_theta, ri) > random_real() {
-            unit_direction.reflect(&hit_rec.normal)
-        } else {
-            unit_direction.refract(&hit_rec.normal, ri)
+        let film = self.thin_film.filter(|_| hit_rec.front_face);
+        let (direction, attenuation) = match film {
+            Some(film) if !cannot_refract => {
+                // The film colours the reflection, so pick a direction by the average
+                // reflectance and reweight each channel
+                let reflectance = film.reflectance(ray_in, cos_theta, [(refraction_index, 0.0); 3]);
+                let p_reflect = ((reflectance.r() + reflectance.g() + reflectance.b()) / 3.0)
+                    .clamp(1e-4, 1.0 - 1e-4);
+                if random_real() < p_reflect {
+                    (
+                        unit_direction.reflect(&hit_rec.normal),
+                        attenuation * reflectance / p_reflect,
+                    )
+                } else {
+                    (
+                        unit_direction.refract(&hit_rec.normal, ri),
+                        attenuation * (Colour::new(1.0, 1.0, 1.0) - reflectance)
+                            / (1.0 - p_reflect),
+                    )
+                }
+            }
+            _ => {
+                let direction = if cannot_refract || self.reflectance(cos_theta, ri) > random_real()
+                {
+                    unit_direction.reflect(&hit_rec.normal)
+                } else {
+                    unit_direction.refract(&hit_rec.normal, ri)
+                };
+                (direction, attenuation)
+            }
         };
 
         Some((Ray::new(hit_rec.p, direction), attenuation))
@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use crate::{
+    hittable::HitRecord,
+    ray::Ray,
+    util::random_real,
+    vec3::{dot, Colour},
+};
+
+use super::{fresnel_dielectric, Material};
+
+/// Layers a glossy `coat` over a `base` material, choosing the coat with the Fresnel
+/// reflectance of a dielectric so it dominates at grazing angles, as with varnished wood
+#[derive(Debug, Clone)]
+pub struct FresnelBlend {
+    base: Arc<dyn Material>,
+    coat: Arc<dyn Material>,
+    refraction_index: f64,
+}
+
+impl FresnelBlend {
+    pub fn new(base: Arc<dyn Material>, coat: Arc<dyn Material>, refraction_index: f64) -> Self {
+        Self {
+            base,
+            coat,
+            refraction_index,
+        }
+    }
+}
+
+impl Material for FresnelBlend {
+    fn scatter(&self, ray_in: &Ray, hit_rec: &HitRecord) -> Option<(Ray, Colour)> {
+        let cos_theta = dot(&-ray_in.direction().unit_vector(), &hit_rec.normal);
+        if random_real() < fresnel_dielectric(cos_theta, self.refraction_index) {
+            self.coat.scatter(ray_in, hit_rec)
+        } else {
+            self.base.scatter(ray_in, hit_rec)
+        }
+    }
+
+    fn opacity(&self, hit_rec: &HitRecord) -> f64 {
+        self.base.opacity(hit_rec)
+    }
+}
@@ -0,0 +1,49 @@
+use crate::{
+    hittable::HitRecord,
+    onb::Onb,
+    ray::Ray,
+    util::{random_real, PI},
+    vec3::{Colour, Vec3},
+};
+
+use super::Material;
+
+/// Anisotropic phase function for participating media. Positive `g` favours forward
+/// scattering, negative `g` back scattering and zero is isotropic
+#[derive(Debug, Clone)]
+pub struct HenyeyGreenstein {
+    albedo: Colour,
+    g: f64,
+}
+
+impl HenyeyGreenstein {
+    pub fn new(albedo: Colour, g: f64) -> Self {
+        Self {
+            albedo,
+            g: g.clamp(-0.999, 0.999),
+        }
+    }
+
+    fn sample_cos_theta(&self) -> f64 {
+        let xi = random_real();
+        if self.g.abs() < 1e-3 {
+            return 1.0 - 2.0 * xi;
+        }
+        let sqr = (1.0 - self.g * self.g) / (1.0 - self.g + 2.0 * self.g * xi);
+        ((1.0 + self.g * self.g - sqr * sqr) / (2.0 * self.g)).clamp(-1.0, 1.0)
+    }
+}
+
+impl Material for HenyeyGreenstein {
+    fn scatter(&self, ray_in: &Ray, hit_rec: &HitRecord) -> Option<(Ray, Colour)> {
+        let cos_theta = self.sample_cos_theta();
+        let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+        let phi = 2.0 * PI * random_real();
+
+        // Angles are relative to the direction the ray was already travelling in
+        let local = Vec3::new(sin_theta * phi.cos(), sin_theta * phi.sin(), cos_theta);
+        let direction = Onb::new(&ray_in.direction()).transform(&local);
+
+        Some((Ray::new(hit_rec.p, direction), self.albedo))
+    }
+}
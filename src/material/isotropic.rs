@@ -0,0 +1,29 @@
+use crate::{
+    hittable::HitRecord,
+    ray::Ray,
+    vec3::{Colour, Vec3},
+};
+
+use super::Material;
+
+#[derive(Debug, Clone)]
+pub struct Isotropic {
+    albedo: Colour,
+}
+
+impl Isotropic {
+    pub fn new(albedo: Colour) -> Self {
+        Self { albedo }
+    }
+}
+
+impl Material for Isotropic {
+    fn scatter(
+        &self,
+        #[allow(unused_variables)] ray_in: &Ray,
+        hit_rec: &HitRecord,
+    ) -> Option<(Ray, Colour)> {
+        // Scatter uniformly in all directions
+        Some((Ray::new(hit_rec.p, Vec3::random_unit_vector()), self.albedo))
+    }
+}
@@ -4,12 +4,13 @@ use crate::{
     vec3::{dot, Colour, Vec3},
 };
 
-use super::Material;
+use super::{conductor_from_reflectance, Material, ThinFilm};
 
 #[derive(Debug, Clone)]
 pub struct Metal {
     albedo: Colour,
     fuzz: f64,
+    thin_film: Option<ThinFilm>,
 }
 
 impl Metal {
@@ -17,8 +18,15 @@ impl Metal {
         Self {
             albedo,
             fuzz: fuzz.min(1.0),
+            thin_film: None,
         }
     }
+
+    /// Coats the metal with a thin film, `thickness` given in nanometres
+    pub fn with_thin_film(mut self, thickness: f64, refraction_index: f64) -> Self {
+        self.thin_film = Some(ThinFilm::new(thickness, refraction_index));
+        self
+    }
 }
 
 impl Material for Metal {
@@ -27,7 +35,14 @@ impl Material for Metal {
             + (self.fuzz * Vec3::random_unit_vector());
         let scattered = Ray::new(hit_rec.p, reflected);
         if dot(&scattered.direction(), &hit_rec.normal) > 0.0 {
-            return Some((scattered, self.albedo));
+            let attenuation = match self.thin_film {
+                Some(film) => {
+                    let cos_theta = dot(&-ray_in.direction().unit_vector(), &hit_rec.normal);
+                    film.reflectance(ray_in, cos_theta, conductor_from_reflectance(&self.albedo))
+                }
+                None => self.albedo,
+            };
+            return Some((scattered, attenuation));
         }
         None
     }
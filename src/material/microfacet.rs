@@ -0,0 +1,148 @@
+use crate::{
+    util::{random_real, PI},
+    vec3::{cross, Colour, Vec3},
+};
+
+/// GGX / Trowbridge-Reitz microfacet distribution. Directions are in a local shading frame
+/// with the surface normal along +z
+#[derive(Clone, Copy, Debug)]
+pub struct TrowbridgeReitz {
+    alpha_x: f64,
+    alpha_y: f64,
+}
+
+impl TrowbridgeReitz {
+    pub fn new(alpha_x: f64, alpha_y: f64) -> Self {
+        Self {
+            alpha_x: alpha_x.max(1e-4),
+            alpha_y: alpha_y.max(1e-4),
+        }
+    }
+
+    /// Isotropic distribution from a perceptual roughness in [0, 1]
+    pub fn from_roughness(roughness: f64) -> Self {
+        let alpha = roughness.clamp(0.0, 1.0).powi(2);
+        Self::new(alpha, alpha)
+    }
+
+    /// Below this the surface is treated as a perfect mirror to avoid numerical trouble
+    pub fn is_smooth(&self) -> bool {
+        self.alpha_x.max(self.alpha_y) < 1e-3
+    }
+
+    /// Density of microfacet normals `wm`
+    pub fn d(&self, wm: &Vec3) -> f64 {
+        if wm.z() <= 0.0 {
+            return 0.0;
+        }
+        let x = wm.x() / self.alpha_x;
+        let y = wm.y() / self.alpha_y;
+        let denom = x * x + y * y + wm.z() * wm.z();
+        1.0 / (PI * self.alpha_x * self.alpha_y * denom * denom)
+    }
+
+    /// Smith auxiliary function
+    pub fn lambda(&self, w: &Vec3) -> f64 {
+        let z2 = w.z() * w.z();
+        if z2 == 0.0 {
+            return f64::INFINITY;
+        }
+        let x = self.alpha_x * w.x();
+        let y = self.alpha_y * w.y();
+        let alpha2_tan2 = (x * x + y * y) / z2;
+        0.5 * ((1.0 + alpha2_tan2).sqrt() - 1.0)
+    }
+
+    /// Smith masking function
+    pub fn g1(&self, w: &Vec3) -> f64 {
+        1.0 / (1.0 + self.lambda(w))
+    }
+
+    /// Height correlated Smith masking-shadowing
+    pub fn g(&self, wo: &Vec3, wi: &Vec3) -> f64 {
+        1.0 / (1.0 + self.lambda(wo) + self.lambda(wi))
+    }
+
+    /// Throughput of a path sampled with `sample_wm`, which is G2 / G1(wo) once the
+    /// distribution and sampling density cancel. `wi` may be a transmitted direction as
+    /// masking only depends on its angle to the macro surface
+    pub fn sampled_weight(&self, wo: &Vec3, wi: &Vec3) -> f64 {
+        (1.0 + self.lambda(wo)) / (1.0 + self.lambda(wo) + self.lambda(wi))
+    }
+
+    /// Samples a microfacet normal visible from `wo` (Heitz 2018)
+    pub fn sample_wm(&self, wo: &Vec3) -> Vec3 {
+        // Stretch to the hemisphere configuration
+        let wh = Vec3::new(self.alpha_x * wo.x(), self.alpha_y * wo.y(), wo.z()).unit_vector();
+
+        let lensq = wh.x() * wh.x() + wh.y() * wh.y();
+        let t1 = if lensq > 0.0 {
+            Vec3::new(-wh.y(), wh.x(), 0.0) / lensq.sqrt()
+        } else {
+            Vec3::new(1.0, 0.0, 0.0)
+        };
+        let t2 = cross(&wh, &t1);
+
+        // Sample the projected disk, warping it towards the visible half
+        let r = random_real().sqrt();
+        let phi = 2.0 * PI * random_real();
+        let p1 = r * phi.cos();
+        let s = 0.5 * (1.0 + wh.z());
+        let p2 = (1.0 - s) * (1.0 - p1 * p1).sqrt() + s * r * phi.sin();
+
+        let nh = p1 * t1 + p2 * t2 + (1.0 - p1 * p1 - p2 * p2).max(0.0).sqrt() * wh;
+
+        // Unstretch back to the ellipsoid configuration
+        Vec3::new(
+            self.alpha_x * nh.x(),
+            self.alpha_y * nh.y(),
+            nh.z().max(1e-6),
+        )
+        .unit_vector()
+    }
+}
+
+/// Fresnel reflectance of a conductor with complex index of refraction `eta + i k`
+pub fn fresnel_conductor(cos_theta_i: f64, eta: &Colour, k: &Colour) -> Colour {
+    Colour::new(
+        fresnel_conductor_channel(cos_theta_i, eta.r(), k.r()),
+        fresnel_conductor_channel(cos_theta_i, eta.g(), k.g()),
+        fresnel_conductor_channel(cos_theta_i, eta.b(), k.b()),
+    )
+}
+
+fn fresnel_conductor_channel(cos_theta_i: f64, eta: f64, k: f64) -> f64 {
+    let cos2 = cos_theta_i.clamp(0.0, 1.0).powi(2);
+    let sin2 = 1.0 - cos2;
+    let eta2 = eta * eta;
+    let k2 = k * k;
+
+    let t0 = eta2 - k2 - sin2;
+    let a2_plus_b2 = (t0 * t0 + 4.0 * eta2 * k2).sqrt();
+    let t1 = a2_plus_b2 + cos2;
+    let a = (0.5 * (a2_plus_b2 + t0)).max(0.0).sqrt();
+    let t2 = 2.0 * cos_theta_i * a;
+    let rs = (t1 - t2) / (t1 + t2);
+
+    let t3 = cos2 * a2_plus_b2 + sin2 * sin2;
+    let t4 = t2 * sin2;
+    let rp = rs * (t3 - t4) / (t3 + t4);
+
+    0.5 * (rp + rs)
+}
+
+/// Unpolarised Fresnel reflectance at a boundary where `eta` is the ratio of the refractive
+/// index on the transmitted side over the incident side. Returns 1 on total internal
+/// reflection
+pub fn fresnel_dielectric(cos_theta_i: f64, eta: f64) -> f64 {
+    let cos_theta_i = cos_theta_i.clamp(0.0, 1.0);
+    let sin2_theta_t = (1.0 - cos_theta_i * cos_theta_i) / (eta * eta);
+    if sin2_theta_t >= 1.0 {
+        return 1.0;
+    }
+    let cos_theta_t = (1.0 - sin2_theta_t).sqrt();
+
+    let r_parl = (eta * cos_theta_i - cos_theta_t) / (eta * cos_theta_i + cos_theta_t);
+    let r_perp = (cos_theta_i - eta * cos_theta_t) / (cos_theta_i + eta * cos_theta_t);
+    0.5 * (r_parl * r_parl + r_perp * r_perp)
+}
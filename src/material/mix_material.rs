@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use crate::{
+    hittable::HitRecord,
+    ray::Ray,
+    texture::{SolidColour, Texture},
+    util::random_real,
+    vec3::Colour,
+};
+
+use super::Material;
+
+/// Blends two materials by randomly scattering off one or the other, with `weight` giving the
+/// chance of choosing `second`
+#[derive(Debug, Clone)]
+pub struct MixMaterial {
+    first: Arc<dyn Material>,
+    second: Arc<dyn Material>,
+    weight: Arc<dyn Texture>,
+}
+
+impl MixMaterial {
+    pub fn new(
+        first: Arc<dyn Material>,
+        second: Arc<dyn Material>,
+        weight: Arc<dyn Texture>,
+    ) -> Self {
+        Self {
+            first,
+            second,
+            weight,
+        }
+    }
+
+    pub fn constant(first: Arc<dyn Material>, second: Arc<dyn Material>, weight: f64) -> Self {
+        Self::new(first, second, Arc::new(SolidColour::scalar(weight)))
+    }
+
+    fn weight(&self, hit_rec: &HitRecord) -> f64 {
+        self.weight
+            .scalar(hit_rec.u, hit_rec.v, &hit_rec.p)
+            .clamp(0.0, 1.0)
+    }
+}
+
+impl Material for MixMaterial {
+    fn scatter(&self, ray_in: &Ray, hit_rec: &HitRecord) -> Option<(Ray, Colour)> {
+        if random_real() < self.weight(hit_rec) {
+            self.second.scatter(ray_in, hit_rec)
+        } else {
+            self.first.scatter(ray_in, hit_rec)
+        }
+    }
+
+    fn opacity(&self, hit_rec: &HitRecord) -> f64 {
+        let weight = self.weight(hit_rec);
+        (1.0 - weight) * self.first.opacity(hit_rec) + weight * self.second.opacity(hit_rec)
+    }
+}
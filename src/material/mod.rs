@@ -2,8 +2,14 @@ use std::fmt::Debug;
 
 use crate::{hittable::HitRecord, mod_flat, ray::Ray, vec3::Colour};
 
-mod_flat!(dielectric metal lambertian);
+mod_flat!(alpha_mask bump_map conductor dielectric fresnel_blend henyey_greenstein isotropic metal microfacet mix_material lambertian normal_map principled rough_dielectric thin_film);
 
 pub trait Material: Sync + Send + Debug {
     fn scatter(&self, ray_in: &Ray, hit_rec: &HitRecord) -> Option<(Ray, Colour)>;
+
+    /// How solid the surface is at the hit, from 0 for fully transparent to 1 for fully opaque.
+    /// Checked while intersecting so cut out parts of a surface are never hit
+    fn opacity(&self, #[allow(unused_variables)] hit_rec: &HitRecord) -> f64 {
+        1.0
+    }
 }
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use crate::{
+    hittable::HitRecord,
+    ray::Ray,
+    texture::Texture,
+    vec3::{dot, Colour, Vec3},
+};
+
+use super::Material;
+
+/// Perturbs the shading normal of another material from a tangent space normal map, which
+/// should be loaded with `ImageTexture::from_linear`
+#[derive(Debug, Clone)]
+pub struct NormalMap {
+    inner: Arc<dyn Material>,
+    map: Arc<dyn Texture>,
+    strength: f64,
+}
+
+impl NormalMap {
+    pub fn new(inner: Arc<dyn Material>, map: Arc<dyn Texture>, strength: f64) -> Self {
+        Self {
+            inner,
+            map,
+            strength,
+        }
+    }
+}
+
+impl Material for NormalMap {
+    fn scatter(&self, ray_in: &Ray, hit_rec: &HitRecord) -> Option<(Ray, Colour)> {
+        let encoded = self.map.value(hit_rec.u, hit_rec.v, &hit_rec.p);
+        let (tangent, bitangent) = hit_rec.shading_frame();
+
+        // Decode from [0, 1] to [-1, 1] tangent space
+        let local = Vec3::new(
+            self.strength * (2.0 * encoded.x() - 1.0),
+            self.strength * (2.0 * encoded.y() - 1.0),
+            2.0 * encoded.z() - 1.0,
+        );
+        let shading_normal =
+            local.x() * tangent + local.y() * bitangent + local.z() * hit_rec.normal;
+
+        scatter_with_shading_normal(self.inner.as_ref(), ray_in, hit_rec, &shading_normal)
+    }
+
+    fn opacity(&self, hit_rec: &HitRecord) -> f64 {
+        self.inner.opacity(hit_rec)
+    }
+}
+
+/// Scatters off `inner` as if the surface had `shading_normal`, keeping the result consistent
+/// with the true geometry. A shading normal facing away from the viewer falls back to the
+/// geometric normal, and directions that are on different sides of the two normals are
+/// dropped so light can't leak through the surface
+pub(crate) fn scatter_with_shading_normal(
+    inner: &dyn Material,
+    ray_in: &Ray,
+    hit_rec: &HitRecord,
+    shading_normal: &Vec3,
+) -> Option<(Ray, Colour)> {
+    let geometric_normal = hit_rec.normal;
+    let mut shaded = hit_rec.clone();
+    if !shading_normal.near_zero() && dot(&ray_in.direction(), shading_normal) < 0.0 {
+        shaded.normal = shading_normal.unit_vector();
+    }
+
+    let (scattered, attenuation) = inner.scatter(ray_in, &shaded)?;
+    let side_geometric = dot(&scattered.direction(), &geometric_normal) > 0.0;
+    let side_shading = dot(&scattered.direction(), &shaded.normal) > 0.0;
+    if side_geometric != side_shading {
+        return None;
+    }
+
+    Some((scattered, attenuation))
+}
@@ -0,0 +1,154 @@
+use std::sync::Arc;
+
+use crate::{
+    hittable::HitRecord,
+    onb::Onb,
+    ray::Ray,
+    texture::{SolidColour, Texture},
+    util::random_real,
+    vec3::{dot, Colour, Vec3},
+};
+
+use super::{fresnel_dielectric, Material, RoughDielectric, TrowbridgeReitz};
+
+#[derive(Clone, Debug)]
+pub struct PrincipledOptions {
+    pub base_colour: Arc<dyn Texture>,
+    pub metallic: Arc<dyn Texture>,
+    pub roughness: Arc<dyn Texture>,
+    /// Scales the reflectance of non-metals, 0.5 gives the common 4%
+    pub specular: Arc<dyn Texture>,
+    pub clearcoat: Arc<dyn Texture>,
+    pub clearcoat_roughness: Arc<dyn Texture>,
+    pub sheen: Arc<dyn Texture>,
+    pub transmission: Arc<dyn Texture>,
+    pub refraction_index: f64,
+}
+
+impl Default for PrincipledOptions {
+    fn default() -> Self {
+        Self {
+            base_colour: Arc::new(SolidColour::scalar(0.8)),
+            metallic: Arc::new(SolidColour::scalar(0.0)),
+            roughness: Arc::new(SolidColour::scalar(0.5)),
+            specular: Arc::new(SolidColour::scalar(0.5)),
+            clearcoat: Arc::new(SolidColour::scalar(0.0)),
+            clearcoat_roughness: Arc::new(SolidColour::scalar(0.03)),
+            sheen: Arc::new(SolidColour::scalar(0.0)),
+            transmission: Arc::new(SolidColour::scalar(0.0)),
+            refraction_index: 1.5,
+        }
+    }
+}
+
+/// Disney principled style material which layers a clearcoat over a blend of metal,
+/// transmissive glass and a diffuse base with a specular sheen. Each scatter picks a single
+/// lobe with probability equal to its weight so the weights cancel
+#[derive(Clone, Debug)]
+pub struct Principled {
+    options: PrincipledOptions,
+}
+
+impl Principled {
+    pub fn new(options: &PrincipledOptions) -> Self {
+        Self {
+            options: options.clone(),
+        }
+    }
+}
+
+impl Material for Principled {
+    fn scatter(&self, ray_in: &Ray, hit_rec: &HitRecord) -> Option<(Ray, Colour)> {
+        let (u, v, p) = (hit_rec.u, hit_rec.v, &hit_rec.p);
+        let param = |texture: &Arc<dyn Texture>| texture.scalar(u, v, p).clamp(0.0, 1.0);
+
+        let base_colour = self.options.base_colour.value(u, v, p);
+        let roughness = param(&self.options.roughness);
+        let transmission = param(&self.options.transmission);
+
+        // Paths inside a transmissive object can only meet the glass lobe on the way out. Light
+        // is tinted once as it refracts in, so leaving again or reflecting off the outside
+        // doesn't tint it a second time
+        let glass = || {
+            RoughDielectric::new(self.options.refraction_index, roughness)
+                .scatter(ray_in, hit_rec)
+                .map(|(ray, attenuation)| {
+                    if hit_rec.front_face && dot(&ray.direction(), &hit_rec.normal) < 0.0 {
+                        (ray, attenuation * base_colour)
+                    } else {
+                        (ray, attenuation)
+                    }
+                })
+        };
+        if !hit_rec.front_face && transmission > 0.0 {
+            return glass();
+        }
+
+        let frame = Onb::new(&hit_rec.normal);
+        let wo = frame.local(&-ray_in.direction().unit_vector());
+        if wo.z() <= 0.0 {
+            return None;
+        }
+        let reflect = |distribution: TrowbridgeReitz, tint: &dyn Fn(f64) -> Colour| {
+            let (wi, cos_h) = sample_reflection(&distribution, &wo)?;
+            let weight = tint(cos_h) * distribution.sampled_weight(&wo, &wi);
+            Some((Ray::new(hit_rec.p, frame.transform(&wi)), weight))
+        };
+
+        let clearcoat = param(&self.options.clearcoat);
+        if random_real() < clearcoat * fresnel_dielectric(wo.z(), 1.5) {
+            let distribution =
+                TrowbridgeReitz::from_roughness(param(&self.options.clearcoat_roughness));
+            return reflect(distribution, &|_| Colour::new(1.0, 1.0, 1.0));
+        }
+
+        let distribution = TrowbridgeReitz::from_roughness(roughness);
+        if random_real() < param(&self.options.metallic) {
+            return reflect(distribution, &|cos_h| schlick(&base_colour, cos_h));
+        }
+
+        if random_real() < transmission {
+            return glass();
+        }
+
+        let f0 = 0.08 * param(&self.options.specular);
+        if random_real() < schlick(&Colour::new(f0, f0, f0), wo.z()).r() {
+            return reflect(distribution, &|_| Colour::new(1.0, 1.0, 1.0));
+        }
+
+        // Cosine weighted diffuse, with sheen brightening grazing angles
+        let mut wi = Vec3::new(0.0, 0.0, 1.0) + Vec3::random_unit_vector();
+        if wi.near_zero() {
+            wi = Vec3::new(0.0, 0.0, 1.0);
+        }
+        let wi = wi.unit_vector();
+        let cos_d = dot(&wi, &(wi + wo).unit_vector());
+        let sheen = param(&self.options.sheen) * (1.0 - cos_d).clamp(0.0, 1.0).powi(5);
+        let attenuation = base_colour + Colour::new(sheen, sheen, sheen);
+        let attenuation = Colour::new(
+            attenuation.r().min(1.0),
+            attenuation.g().min(1.0),
+            attenuation.b().min(1.0),
+        );
+
+        Some((Ray::new(hit_rec.p, frame.transform(&wi)), attenuation))
+    }
+}
+
+/// Mirror `wo` about a sampled microfacet normal, returning the new direction and the cosine
+/// between it and the microfacet normal
+fn sample_reflection(distribution: &TrowbridgeReitz, wo: &Vec3) -> Option<(Vec3, f64)> {
+    let wm = if distribution.is_smooth() {
+        Vec3::new(0.0, 0.0, 1.0)
+    } else {
+        distribution.sample_wm(wo)
+    };
+    let cos_h = dot(wo, &wm);
+    let wi = -*wo + 2.0 * cos_h * wm;
+    (wi.z() > 0.0).then_some((wi, cos_h))
+}
+
+fn schlick(f0: &Colour, cos_theta: f64) -> Colour {
+    let weight = (1.0 - cos_theta.clamp(0.0, 1.0)).powi(5);
+    *f0 + weight * (Colour::new(1.0, 1.0, 1.0) - *f0)
+}
@@ -0,0 +1,78 @@
+use crate::{
+    hittable::HitRecord,
+    onb::Onb,
+    ray::Ray,
+    util::random_real,
+    vec3::{dot, Colour, Vec3},
+};
+
+use super::{fresnel_dielectric, Material, TrowbridgeReitz};
+
+/// Glass with a GGX microfacet surface which both reflects and transmits through the sampled
+/// microfacet (Walter et al. 2007), giving frosted or etched glass
+#[derive(Debug, Clone)]
+pub struct RoughDielectric {
+    refraction_index: f64,
+    distribution: TrowbridgeReitz,
+}
+
+impl RoughDielectric {
+    pub fn new(refraction_index: f64, roughness: f64) -> Self {
+        Self {
+            refraction_index,
+            distribution: TrowbridgeReitz::from_roughness(roughness),
+        }
+    }
+}
+
+impl Material for RoughDielectric {
+    fn scatter(&self, ray_in: &Ray, hit_rec: &HitRecord) -> Option<(Ray, Colour)> {
+        let attenuation = Colour::new(1.0, 1.0, 1.0);
+        let eta = if hit_rec.front_face {
+            self.refraction_index
+        } else {
+            1.0 / self.refraction_index
+        };
+
+        let frame = Onb::new(&hit_rec.normal);
+        let wo = frame.local(&-ray_in.direction().unit_vector());
+        if wo.z() <= 0.0 {
+            return None;
+        }
+
+        let wm = if self.distribution.is_smooth() {
+            Vec3::new(0.0, 0.0, 1.0)
+        } else {
+            self.distribution.sample_wm(&wo)
+        };
+        let cos_o = dot(&wo, &wm);
+
+        // Choose between reflection and transmission in proportion to the Fresnel term so it
+        // cancels out of the weight
+        let reflectance = fresnel_dielectric(cos_o, eta);
+        let wi = if random_real() < reflectance {
+            let wi = -wo + 2.0 * cos_o * wm;
+            if wi.z() <= 0.0 {
+                return None;
+            }
+            wi
+        } else {
+            let cos_t = (1.0 - (1.0 - cos_o * cos_o) / (eta * eta)).sqrt();
+            let wi = -wo / eta + (cos_o / eta - cos_t) * wm;
+            if wi.z() >= 0.0 {
+                return None;
+            }
+            wi
+        };
+
+        let weight = if self.distribution.is_smooth() {
+            1.0
+        } else {
+            self.distribution.sampled_weight(&wo, &wi)
+        };
+        Some((
+            Ray::new(hit_rec.p, frame.transform(&wi)),
+            weight * attenuation,
+        ))
+    }
+}
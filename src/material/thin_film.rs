@@ -0,0 +1,179 @@
+use std::ops::{Add, Div, Mul, Sub};
+
+use crate::{ray::Ray, util::PI, vec3::Colour};
+
+/// Wavelengths in nanometres standing in for the red, green and blue channels outside of
+/// spectral rendering
+const RGB_WAVELENGTHS: [f64; 3] = [630.0, 532.0, 465.0];
+
+/// Thin transparent coating whose reflections interfere with each other, giving the
+/// iridescence of soap bubbles and lens coatings
+#[derive(Clone, Copy, Debug)]
+pub struct ThinFilm {
+    /// Film thickness in nanometres
+    pub thickness: f64,
+    pub refraction_index: f64,
+}
+
+impl ThinFilm {
+    pub fn new(thickness: f64, refraction_index: f64) -> Self {
+        Self {
+            thickness: thickness.max(0.0),
+            refraction_index,
+        }
+    }
+
+    /// Reflectance of the film in air over a substrate with complex refractive index, per
+    /// channel. Spectral rays are evaluated at their own wavelength
+    pub fn reflectance(
+        &self,
+        ray_in: &Ray,
+        cos_theta_i: f64,
+        substrate: [(f64, f64); 3],
+    ) -> Colour {
+        if let Some(wavelength) = ray_in.wavelength() {
+            // Pick the substrate channel nearest in wavelength
+            let channel = RGB_WAVELENGTHS
+                .iter()
+                .enumerate()
+                .min_by(|a, b| {
+                    (a.1 - wavelength)
+                        .abs()
+                        .total_cmp(&(b.1 - wavelength).abs())
+                })
+                .map_or(1, |(i, _)| i);
+            let r = self.reflectance_at(cos_theta_i, substrate[channel], wavelength);
+            return Colour::new(r, r, r);
+        }
+
+        Colour::new(
+            self.reflectance_at(cos_theta_i, substrate[0], RGB_WAVELENGTHS[0]),
+            self.reflectance_at(cos_theta_i, substrate[1], RGB_WAVELENGTHS[1]),
+            self.reflectance_at(cos_theta_i, substrate[2], RGB_WAVELENGTHS[2]),
+        )
+    }
+
+    /// Airy summation of the multiple reflections inside the film, averaged over both
+    /// polarisations
+    fn reflectance_at(&self, cos_theta_i: f64, substrate: (f64, f64), wavelength: f64) -> f64 {
+        let n1 = Complex::real(1.0);
+        let n2 = Complex::real(self.refraction_index);
+        let n3 = Complex::new(substrate.0, substrate.1);
+
+        let cos1 = Complex::real(cos_theta_i.clamp(0.0, 1.0));
+        let sin1_sq = 1.0 - cos_theta_i * cos_theta_i;
+        let cos2 = snell_cos(n1, n2, sin1_sq);
+        let cos3 = snell_cos(n1, n3, sin1_sq);
+
+        // Phase difference picked up by one round trip through the film
+        let delta = n2 * cos2 * Complex::real(4.0 * PI * self.thickness / wavelength);
+        let phase = delta.exp_i();
+
+        let airy = |r12: Complex, r23: Complex| {
+            let r = (r12 + r23 * phase) / (Complex::real(1.0) + r12 * r23 * phase);
+            r.norm_sqr()
+        };
+
+        let rs = airy(fresnel_s(n1, cos1, n2, cos2), fresnel_s(n2, cos2, n3, cos3));
+        let rp = airy(fresnel_p(n1, cos1, n2, cos2), fresnel_p(n2, cos2, n3, cos3));
+        (0.5 * (rs + rp)).clamp(0.0, 1.0)
+    }
+}
+
+/// Complex refractive index approximating a metal of the given reflectance at normal
+/// incidence (Gulbrandsen 2014, with no extra edge tint)
+pub fn conductor_from_reflectance(reflectance: &Colour) -> [(f64, f64); 3] {
+    let channel = |r: f64| {
+        let r = r.clamp(0.0, 0.999);
+        let n = (1.0 - r) / (1.0 + r);
+        let k2 = (r * (n + 1.0).powi(2) - (n - 1.0).powi(2)) / (1.0 - r);
+        (n, k2.max(0.0).sqrt())
+    };
+    [
+        channel(reflectance.r()),
+        channel(reflectance.g()),
+        channel(reflectance.b()),
+    ]
+}
+
+fn snell_cos(n_i: Complex, n_t: Complex, sin_i_sq: f64) -> Complex {
+    let ratio = n_i / n_t;
+    (Complex::real(1.0) - ratio * ratio * Complex::real(sin_i_sq)).sqrt()
+}
+
+fn fresnel_s(n_i: Complex, cos_i: Complex, n_t: Complex, cos_t: Complex) -> Complex {
+    (n_i * cos_i - n_t * cos_t) / (n_i * cos_i + n_t * cos_t)
+}
+
+fn fresnel_p(n_i: Complex, cos_i: Complex, n_t: Complex, cos_t: Complex) -> Complex {
+    (n_t * cos_i - n_i * cos_t) / (n_t * cos_i + n_i * cos_t)
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Complex {
+    re: f64,
+    im: f64,
+}
+
+impl Complex {
+    fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    fn real(re: f64) -> Self {
+        Self::new(re, 0.0)
+    }
+
+    fn norm_sqr(&self) -> f64 {
+        self.re * self.re + self.im * self.im
+    }
+
+    /// e^(i self)
+    fn exp_i(&self) -> Self {
+        let magnitude = (-self.im).exp();
+        Self::new(magnitude * self.re.cos(), magnitude * self.re.sin())
+    }
+
+    /// Principal square root
+    fn sqrt(&self) -> Self {
+        let magnitude = self.norm_sqr().sqrt();
+        let re = (0.5 * (magnitude + self.re)).max(0.0).sqrt();
+        let im = (0.5 * (magnitude - self.re)).max(0.0).sqrt();
+        Self::new(re, if self.im < 0.0 { -im } else { im })
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+    fn add(self, rhs: Self) -> Self::Output {
+        Complex::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Complex::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Complex::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl Div for Complex {
+    type Output = Complex;
+    fn div(self, rhs: Self) -> Self::Output {
+        let denom = rhs.norm_sqr();
+        Complex::new(
+            (self.re * rhs.re + self.im * rhs.im) / denom,
+            (self.im * rhs.re - self.re * rhs.im) / denom,
+        )
+    }
+}
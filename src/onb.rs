@@ -0,0 +1,48 @@
+use crate::vec3::{cross, dot, Vec3};
+
+/// Orthonormal basis built around a single direction, used to move sampled directions from a
+/// local frame (with `w` as the z axis) into world space
+#[derive(Clone, Copy, Debug)]
+pub struct Onb {
+    axis: [Vec3; 3],
+}
+
+impl Onb {
+    pub fn new(n: &Vec3) -> Self {
+        let w = n.unit_vector();
+        let a = if w.x().abs() > 0.9 {
+            Vec3::new(0.0, 1.0, 0.0)
+        } else {
+            Vec3::new(1.0, 0.0, 0.0)
+        };
+        let v = cross(&w, &a).unit_vector();
+        let u = cross(&w, &v);
+        Self { axis: [u, v, w] }
+    }
+
+    pub fn u(&self) -> Vec3 {
+        self.axis[0]
+    }
+
+    pub fn v(&self) -> Vec3 {
+        self.axis[1]
+    }
+
+    pub fn w(&self) -> Vec3 {
+        self.axis[2]
+    }
+
+    /// Transform from local coordinates into world space
+    pub fn transform(&self, v: &Vec3) -> Vec3 {
+        v.x() * self.axis[0] + v.y() * self.axis[1] + v.z() * self.axis[2]
+    }
+
+    /// Transform from world space into local coordinates
+    pub fn local(&self, v: &Vec3) -> Vec3 {
+        Vec3::new(
+            dot(v, &self.axis[0]),
+            dot(v, &self.axis[1]),
+            dot(v, &self.axis[2]),
+        )
+    }
+}
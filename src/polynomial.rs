@@ -0,0 +1,123 @@
+//! Real root finding for low order polynomials. Roots are isolated by recursively solving the
+//! derivative, which splits the real line into monotonic pieces, then each piece containing a
+//! sign change is refined by bisection. This is slower than the closed form solutions but does
+//! not suffer from their catastrophic cancellation, which shows up as speckles on tori.
+
+const MAX_ITERATIONS: usize = 100;
+
+/// Real roots of `coeffs[0] + coeffs[1] * x + ... + coeffs[n] * x^n` in ascending order
+pub fn solve(coeffs: &[f64]) -> Vec<f64> {
+    // Strip vanishing leading coefficients so the degree is accurate
+    let mut degree = coeffs.len().saturating_sub(1);
+    while degree > 0 && coeffs[degree].abs() < 1e-300 {
+        degree -= 1;
+    }
+    let coeffs = &coeffs[..=degree];
+
+    match degree {
+        0 => Vec::new(),
+        1 => vec![-coeffs[0] / coeffs[1]],
+        2 => solve_quadratic(coeffs[2], coeffs[1], coeffs[0]),
+        _ => {
+            let bound = cauchy_bound(coeffs);
+            let derivative: Vec<f64> = coeffs
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, c)| i as f64 * c)
+                .collect();
+
+            let mut edges = vec![-bound];
+            edges.extend(solve(&derivative).into_iter().filter(|x| x.abs() < bound));
+            edges.push(bound);
+
+            let mut roots: Vec<f64> = edges
+                .windows(2)
+                .filter_map(|w| bisect(coeffs, w[0], w[1]))
+                .collect();
+            // A root on a shared edge is found from both sides
+            roots.dedup_by(|a, b| (*a - *b).abs() < 1e-12);
+            roots
+        }
+    }
+}
+
+/// Real roots of `a * t^4 + b * t^3 + c * t^2 + d * t + e` in ascending order
+pub fn solve_quartic(a: f64, b: f64, c: f64, d: f64, e: f64) -> Vec<f64> {
+    solve(&[e, d, c, b, a])
+}
+
+fn solve_quadratic(a: f64, b: f64, c: f64) -> Vec<f64> {
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return Vec::new();
+    }
+
+    // Avoid subtracting nearly equal values
+    let q = -0.5 * (b + b.signum() * discriminant.sqrt());
+    let (r0, r1) = if q == 0.0 { (0.0, 0.0) } else { (q / a, c / q) };
+    vec![r0.min(r1), r0.max(r1)]
+}
+
+fn evaluate(coeffs: &[f64], x: f64) -> f64 {
+    coeffs.iter().rev().fold(0.0, |acc, c| acc * x + c)
+}
+
+fn cauchy_bound(coeffs: &[f64]) -> f64 {
+    let leading = coeffs[coeffs.len() - 1];
+    1.0 + coeffs[..coeffs.len() - 1]
+        .iter()
+        .fold(0.0_f64, |acc, c| acc.max((c / leading).abs()))
+}
+
+fn bisect(coeffs: &[f64], mut lo: f64, mut hi: f64) -> Option<f64> {
+    let mut f_lo = evaluate(coeffs, lo);
+    let f_hi = evaluate(coeffs, hi);
+    if f_lo == 0.0 {
+        return Some(lo);
+    }
+    if f_lo.signum() == f_hi.signum() {
+        return None;
+    }
+
+    for _ in 0..MAX_ITERATIONS {
+        let mid = 0.5 * (lo + hi);
+        if mid <= lo || mid >= hi {
+            break;
+        }
+        let f_mid = evaluate(coeffs, mid);
+        if f_mid == 0.0 {
+            return Some(mid);
+        }
+        if f_mid.signum() == f_lo.signum() {
+            lo = mid;
+            f_lo = f_mid;
+        } else {
+            hi = mid;
+        }
+    }
+
+    Some(0.5 * (lo + hi))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quartic_with_four_real_roots() {
+        // (x - 1)(x - 2)(x - 3)(x - 4)
+        let mut roots = solve_quartic(1.0, -10.0, 35.0, -50.0, 24.0);
+        roots.sort_by(f64::total_cmp);
+        assert_eq!(roots.len(), 4);
+        for (root, expected) in roots.into_iter().zip([1.0, 2.0, 3.0, 4.0]) {
+            assert!((root - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn quartic_with_no_real_roots() {
+        // x^4 + 1
+        assert!(solve_quartic(1.0, 0.0, 0.0, 0.0, 1.0).is_empty());
+    }
+}
@@ -4,6 +4,7 @@ use crate::vec3::{Point3, Vec3};
 pub struct Ray {
     orig: Point3,
     dir: Vec3,
+    wavelength: Option<f64>,
 }
 
 impl Ray {
@@ -11,9 +12,16 @@ impl Ray {
         Self {
             orig: origin,
             dir: direction,
+            wavelength: None,
         }
     }
 
+    /// Tags the ray with a single wavelength in nanometres, for spectral rendering
+    pub fn with_wavelength(mut self, wavelength: f64) -> Self {
+        self.wavelength = Some(wavelength);
+        self
+    }
+
     pub fn origin(&self) -> Point3 {
         self.orig
     }
@@ -22,6 +30,10 @@ impl Ray {
         self.dir
     }
 
+    pub fn wavelength(&self) -> Option<f64> {
+        self.wavelength
+    }
+
     pub fn at(&self, t: f64) -> Point3 {
         self.orig + t * self.dir
     }
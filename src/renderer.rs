@@ -1,10 +1,11 @@
 use std::fmt::Debug;
 
 use crate::{
-    camera::Camera,
-    grid::Grid,
+    camera::{Camera, CameraPath, Eye, StereoRig},
+    grid::{Grid, Region},
     hittable::{Hittable, HittableList},
     ray::Ray,
+    spectrum::{rgb_to_spectrum, sample_wavelength, wavelength_to_linear_srgb},
     util::Interval,
     vec3::{Colour, Vec3},
 };
@@ -29,37 +30,122 @@ impl Renderer {
     ) where
         F: Sync + Send + Fn(&Ray, i32, &HittableList) -> Colour,
     {
-        let render_fn = self.render(world, ray_colour);
+        let mut camera = self.camera.clone();
+        camera.focus_on(&world);
+        let render_fn = self.render(&camera, &world, &ray_colour);
         pixels.set_all_parallel(render_fn);
         // If benchmarking we don't need to render the image out
         #[cfg(not(feature = "benchmark"))]
-        self.output_img(pixels);
+        self.output_img(pixels, &self.filename);
     }
 
-    fn render<F>(
+    /// Renders only the pixels inside `region`, with the camera still framing the whole image,
+    /// then writes out the full grid. Passing a grid loaded with `Grid::from_image` composites
+    /// the region into that earlier render
+    pub fn render_region<F, const W: usize, const H: usize>(
         &self,
         world: HittableList,
         ray_colour: F,
-    ) -> impl Send + Sync + Fn(usize, usize) -> [u8; 3]
+        region: &Region,
+        mut pixels: Grid<[u8; 3], W, H>,
+    ) where
+        F: Sync + Send + Fn(&Ray, i32, &HittableList) -> Colour,
+    {
+        let mut camera = self.camera.clone();
+        camera.focus_on(&world);
+        pixels.set_region_parallel(region, self.render(&camera, &world, &ray_colour));
+        #[cfg(not(feature = "benchmark"))]
+        self.output_img(pixels, &self.filename);
+    }
+
+    /// Renders both eyes of `rig` into one image laid out as the rig specifies, each eye taking
+    /// its half of `pixels`
+    pub fn render_stereo<F, const W: usize, const H: usize>(
+        &self,
+        world: HittableList,
+        ray_colour: F,
+        rig: &StereoRig,
+        mut pixels: Grid<[u8; 3], W, H>,
+    ) where
+        F: Sync + Send + Fn(&Ray, i32, &HittableList) -> Colour,
+    {
+        for eye in [Eye::Left, Eye::Right] {
+            info!("rendering {eye:?} eye");
+
+            let region = rig.region(eye, W, H);
+            let mut camera = rig.eye_camera(&self.camera, eye, W, H);
+            camera.focus_on(&world);
+            let render_fn = self.render(&camera, &world, &ray_colour);
+            pixels.set_region_parallel(&region, |x, y| render_fn(x - region.x, y - region.y));
+        }
+        #[cfg(not(feature = "benchmark"))]
+        self.output_img(pixels, &self.filename);
+    }
+
+    /// Renders `frames` images evenly spaced over the duration of `path`, numbering each
+    /// frame's file after `filename`. The world is built once and shared by every frame, and
+    /// camera settings the path doesn't animate come from `camera`
+    pub fn render_sequence<F, const W: usize, const H: usize>(
+        &self,
+        world: HittableList,
+        ray_colour: F,
+        path: &CameraPath,
+        frames: usize,
+    ) where
+        F: Sync + Send + Fn(&Ray, i32, &HittableList) -> Colour,
+    {
+        let Some((start, end)) = path.duration() else {
+            return;
+        };
+        for frame in 0..frames {
+            info!("rendering frame {} of {frames}", frame + 1);
+
+            let time = if frames > 1 {
+                start + (end - start) * frame as f64 / (frames - 1) as f64
+            } else {
+                start
+            };
+            let mut camera = path.camera_at(time, &self.camera);
+            camera.focus_on(&world);
+
+            let mut pixels: Grid<[u8; 3], W, H> = Default::default();
+            pixels.set_all_parallel(self.render(&camera, &world, &ray_colour));
+            #[cfg(not(feature = "benchmark"))]
+            self.output_img(pixels, &frame_filename(&self.filename, frame));
+        }
+    }
+
+    fn render<'a, F>(
+        &self,
+        camera: &'a Camera,
+        world: &'a HittableList,
+        ray_colour: &'a F,
+    ) -> impl Send + Sync + Fn(usize, usize) -> [u8; 3] + 'a
     where
         F: Sync + Send + Fn(&Ray, i32, &HittableList) -> Colour,
     {
         let samples = self.samples;
-        let camera = self.camera;
         let depth = self.max_depth;
         move |x, y| {
             let sample_rays = (0..samples).into_par_iter().map(|_| {
-                let ray = camera.hit_ray(x, y);
-                ray_colour(&ray, depth, &world)
+                camera
+                    .hit_ray(x, y)
+                    .map_or(Colour::new(0.0, 0.0, 0.0), |(ray, weight)| {
+                        weight * ray_colour(&ray, depth, world)
+                    })
             });
 
-            let avg_color = sample_rays.sum::<Vec3>() * (1.0 / samples as f64);
+            let avg_color = sample_rays.sum::<Vec3>() * (camera.exposure / samples as f64);
             avg_color.to_rgb_gamma_corrected()
         }
     }
 
     #[cfg(not(feature = "benchmark"))]
-    fn output_img<const W: usize, const H: usize>(&self, pixels: Grid<[u8; 3], W, H>) {
+    fn output_img<const W: usize, const H: usize>(
+        &self,
+        pixels: Grid<[u8; 3], W, H>,
+        filename: &str,
+    ) {
         info!("beginning image write");
 
         let mut img_buf = image::ImageBuffer::new(pixels.width() as u32, pixels.height() as u32);
@@ -75,11 +161,26 @@ impl Renderer {
             span_header.pb_inc(1);
         }
 
-        img_buf.save(&self.filename).expect("writing image");
+        img_buf.save(filename).expect("writing image");
         std::mem::drop(span_header_entered);
         std::mem::drop(span_header);
     }
 }
+/// `filename` with a zero padded frame number added before the extension
+#[cfg(not(feature = "benchmark"))]
+fn frame_filename(filename: &str, frame: usize) -> String {
+    let path = std::path::Path::new(filename);
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    let name = match path.extension().and_then(|e| e.to_str()) {
+        Some(extension) => format!("{stem}_{frame:04}.{extension}"),
+        None => format!("{stem}_{frame:04}"),
+    };
+    path.with_file_name(name).to_string_lossy().into_owned()
+}
+
 pub fn ray_colour(r: &Ray, depth: i32, world: &impl Hittable) -> Colour {
     if depth <= 0 {
         return Colour::new(0.0, 0.0, 0.0);
@@ -93,6 +194,39 @@ pub fn ray_colour(r: &Ray, depth: i32, world: &impl Hittable) -> Colour {
         return Colour::new(0.0, 0.0, 0.0);
     }
 
+    sky_colour(r)
+}
+
+/// Spectral alternative to `ray_colour`, which traces a single randomly chosen wavelength per
+/// path so wavelength dependent materials such as dispersive glass split light, then returns
+/// that sample's contribution in linear RGB
+pub fn spectral_ray_colour(r: &Ray, depth: i32, world: &impl Hittable) -> Colour {
+    let wavelength = r.wavelength().unwrap_or_else(sample_wavelength);
+    let ray = r.with_wavelength(wavelength);
+    wavelength_to_linear_srgb(spectral_radiance(&ray, depth, world), wavelength)
+}
+
+fn spectral_radiance(r: &Ray, depth: i32, world: &impl Hittable) -> f64 {
+    if depth <= 0 {
+        return 0.0;
+    }
+    // Rays are always tagged by spectral_ray_colour before getting here
+    let wavelength = r.wavelength().unwrap_or_default();
+    if let Some(hit) = world.hit(r, Interval::new(0.001, f64::INFINITY)) {
+        if let Some(material) = hit.material.clone() {
+            if let Some((scattered, attenuation)) = material.scatter(r, &hit) {
+                let scattered = scattered.with_wavelength(wavelength);
+                return rgb_to_spectrum(&attenuation, wavelength)
+                    * spectral_radiance(&scattered, depth - 1, world);
+            }
+        }
+        return 0.0;
+    }
+
+    rgb_to_spectrum(&sky_colour(r), wavelength)
+}
+
+fn sky_colour(r: &Ray) -> Colour {
     let unit_direction = r.direction().unit_vector();
     let a = 0.5 * (unit_direction.y() + 1.0);
     (1.0 - a) * Colour::new(1.0, 1.0, 1.0) + a * Colour::new(0.5, 0.7, 1.0)
@@ -0,0 +1,94 @@
+use crate::vec3::{Point3, Vec3};
+
+use super::Sdf;
+
+#[derive(Debug)]
+pub struct Union(pub Box<dyn Sdf>, pub Box<dyn Sdf>);
+
+impl Sdf for Union {
+    fn distance(&self, p: &Point3) -> f64 {
+        self.0.distance(p).min(self.1.distance(p))
+    }
+}
+
+#[derive(Debug)]
+pub struct Intersection(pub Box<dyn Sdf>, pub Box<dyn Sdf>);
+
+impl Sdf for Intersection {
+    fn distance(&self, p: &Point3) -> f64 {
+        self.0.distance(p).max(self.1.distance(p))
+    }
+}
+
+/// Everything inside the first shape that is not inside the second
+#[derive(Debug)]
+pub struct Subtraction(pub Box<dyn Sdf>, pub Box<dyn Sdf>);
+
+impl Sdf for Subtraction {
+    fn distance(&self, p: &Point3) -> f64 {
+        self.0.distance(p).max(-self.1.distance(p))
+    }
+}
+
+/// Union which blends the two shapes together wherever they come within `k` of each other
+#[derive(Debug)]
+pub struct SmoothUnion {
+    pub a: Box<dyn Sdf>,
+    pub b: Box<dyn Sdf>,
+    pub k: f64,
+}
+
+impl Sdf for SmoothUnion {
+    fn distance(&self, p: &Point3) -> f64 {
+        let d1 = self.a.distance(p);
+        let d2 = self.b.distance(p);
+        if self.k <= 0.0 {
+            return d1.min(d2);
+        }
+        let h = (0.5 + 0.5 * (d2 - d1) / self.k).clamp(0.0, 1.0);
+        d2 + (d1 - d2) * h - self.k * h * (1.0 - h)
+    }
+}
+
+/// Infinitely tiles a shape on a grid with the given spacing, any zero spacing component
+/// leaves that axis untiled. The shape should fit inside a single cell
+#[derive(Debug)]
+pub struct Repeat {
+    pub sdf: Box<dyn Sdf>,
+    pub spacing: Vec3,
+}
+
+impl Sdf for Repeat {
+    fn distance(&self, p: &Point3) -> f64 {
+        let wrap = |x: f64, s: f64| {
+            if s > 0.0 {
+                x - s * (x / s).round()
+            } else {
+                x
+            }
+        };
+        let q = Point3::new(
+            wrap(p.x(), self.spacing.x()),
+            wrap(p.y(), self.spacing.y()),
+            wrap(p.z(), self.spacing.z()),
+        );
+        self.sdf.distance(&q)
+    }
+}
+
+/// Twists a shape around the y axis by `rate` radians per unit of height. This bends the
+/// distance field so the shape needs a step scale below one to trace correctly
+#[derive(Debug)]
+pub struct Twist {
+    pub sdf: Box<dyn Sdf>,
+    pub rate: f64,
+}
+
+impl Sdf for Twist {
+    fn distance(&self, p: &Point3) -> f64 {
+        let angle = self.rate * p.y();
+        let (sin, cos) = angle.sin_cos();
+        let q = Point3::new(cos * p.x() - sin * p.z(), p.y(), sin * p.x() + cos * p.z());
+        self.sdf.distance(&q)
+    }
+}
@@ -0,0 +1,53 @@
+use crate::vec3::{Point3, Vec3};
+
+use super::Sdf;
+
+/// Mandelbulb fractal of the given `power` centred on the origin, fitting inside a sphere of
+/// radius roughly 1.2
+#[derive(Clone, Debug)]
+pub struct Mandelbulb {
+    pub power: f64,
+    pub iterations: usize,
+}
+
+impl Default for Mandelbulb {
+    fn default() -> Self {
+        Self {
+            power: 8.0,
+            iterations: 12,
+        }
+    }
+}
+
+impl Sdf for Mandelbulb {
+    fn distance(&self, p: &Point3) -> f64 {
+        let mut z = *p;
+        let mut dr = 1.0;
+        let mut r = 0.0;
+
+        for _ in 0..self.iterations {
+            r = z.length();
+            if r > 2.0 {
+                break;
+            }
+
+            // Raise z to the power in spherical coordinates and add the original point
+            let theta = (z.z() / r).acos() * self.power;
+            let phi = z.y().atan2(z.x()) * self.power;
+            dr = r.powf(self.power - 1.0) * self.power * dr + 1.0;
+
+            let zr = r.powf(self.power);
+            z =
+                zr * Vec3::new(
+                    theta.sin() * phi.cos(),
+                    phi.sin() * theta.sin(),
+                    theta.cos(),
+                ) + *p;
+        }
+
+        if r == 0.0 {
+            return 0.0;
+        }
+        0.5 * r.ln() * r / dr
+    }
+}
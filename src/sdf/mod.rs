@@ -0,0 +1,29 @@
+use std::fmt::Debug;
+
+use crate::{mod_flat, vec3::Point3};
+
+mod_flat!(combinators fractal primitives);
+
+pub trait Sdf: Sync + Send + Debug {
+    /// Signed distance from `p` to the surface, negative inside. Sphere tracing relies on this
+    /// never overestimating the true distance
+    fn distance(&self, p: &Point3) -> f64;
+}
+
+/// Arbitrary distance function given as a closure
+pub struct SdfFn<F>(pub F);
+
+impl<F> Sdf for SdfFn<F>
+where
+    F: Sync + Send + Fn(&Point3) -> f64,
+{
+    fn distance(&self, p: &Point3) -> f64 {
+        (self.0)(p)
+    }
+}
+
+impl<F> Debug for SdfFn<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SdfFn")
+    }
+}
@@ -0,0 +1,53 @@
+use crate::vec3::{Point3, Vec3};
+
+use super::Sdf;
+
+#[derive(Clone, Debug)]
+pub struct SdfSphere {
+    pub centre: Point3,
+    pub radius: f64,
+}
+
+impl Sdf for SdfSphere {
+    fn distance(&self, p: &Point3) -> f64 {
+        (*p - self.centre).length() - self.radius
+    }
+}
+
+/// Axis aligned box with rounded edges, `half_extents` measured before rounding
+#[derive(Clone, Debug)]
+pub struct SdfBox {
+    pub centre: Point3,
+    pub half_extents: Vec3,
+    pub rounding: f64,
+}
+
+impl Sdf for SdfBox {
+    fn distance(&self, p: &Point3) -> f64 {
+        let local = *p - self.centre;
+        let q = Vec3::new(
+            local.x().abs() - self.half_extents.x() + self.rounding,
+            local.y().abs() - self.half_extents.y() + self.rounding,
+            local.z().abs() - self.half_extents.z() + self.rounding,
+        );
+        let outside = Vec3::new(q.x().max(0.0), q.y().max(0.0), q.z().max(0.0)).length();
+        let inside = q.x().max(q.y()).max(q.z()).min(0.0);
+        outside + inside - self.rounding
+    }
+}
+
+/// Torus around the y axis
+#[derive(Clone, Debug)]
+pub struct SdfTorus {
+    pub centre: Point3,
+    pub major_radius: f64,
+    pub minor_radius: f64,
+}
+
+impl Sdf for SdfTorus {
+    fn distance(&self, p: &Point3) -> f64 {
+        let local = *p - self.centre;
+        let ring = (local.x() * local.x() + local.z() * local.z()).sqrt() - self.major_radius;
+        (ring * ring + local.y() * local.y()).sqrt() - self.minor_radius
+    }
+}
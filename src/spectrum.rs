@@ -0,0 +1,68 @@
+//! Conversions between RGB and single wavelength samples for the spectral renderer
+
+use std::sync::LazyLock;
+
+use crate::{util::random_real, vec3::Colour};
+
+pub const LAMBDA_MIN: f64 = 380.0;
+pub const LAMBDA_MAX: f64 = 780.0;
+
+/// Linear sRGB of a constant unit spectrum, used to white balance so that spectrally flat
+/// surfaces come out neutral
+static WHITE: LazyLock<Colour> = LazyLock::new(|| {
+    let steps = (LAMBDA_MAX - LAMBDA_MIN) as usize;
+    let xyz = (0..steps)
+        .map(|i| cie_xyz(LAMBDA_MIN + i as f64 + 0.5))
+        .sum::<Colour>();
+    xyz_to_linear_srgb(&xyz)
+});
+
+pub fn sample_wavelength() -> f64 {
+    LAMBDA_MIN + (LAMBDA_MAX - LAMBDA_MIN) * random_real()
+}
+
+/// CIE 1931 colour matching functions from the multi-lobe fit of Wyman, Sloan and Shirley
+pub fn cie_xyz(wavelength: f64) -> Colour {
+    let g = |mu: f64, sigma1: f64, sigma2: f64| {
+        let t = (wavelength - mu) / if wavelength < mu { sigma1 } else { sigma2 };
+        (-0.5 * t * t).exp()
+    };
+
+    Colour::new(
+        1.056 * g(599.8, 37.9, 31.0) + 0.362 * g(442.0, 16.0, 26.7) - 0.065 * g(501.1, 20.4, 26.2),
+        0.821 * g(568.8, 46.9, 40.5) + 0.286 * g(530.9, 16.3, 31.1),
+        1.217 * g(437.0, 11.8, 36.0) + 0.681 * g(459.0, 26.0, 13.8),
+    )
+}
+
+pub fn xyz_to_linear_srgb(xyz: &Colour) -> Colour {
+    let (x, y, z) = (xyz.x(), xyz.y(), xyz.z());
+    Colour::new(
+        3.2404542 * x - 1.5371385 * y - 0.4985314 * z,
+        -0.9692660 * x + 1.8760108 * y + 0.0415560 * z,
+        0.0556434 * x - 0.2040259 * y + 1.0572252 * z,
+    )
+}
+
+/// Linear sRGB estimate of a spectrum from its `value` at a single uniformly sampled
+/// `wavelength`
+pub fn wavelength_to_linear_srgb(value: f64, wavelength: f64) -> Colour {
+    let xyz = cie_xyz(wavelength) * (value * (LAMBDA_MAX - LAMBDA_MIN));
+    xyz_to_linear_srgb(&xyz) * Colour::new(1.0 / WHITE.r(), 1.0 / WHITE.g(), 1.0 / WHITE.b())
+}
+
+/// Evaluates a smooth reflectance spectrum for `rgb` at `wavelength`. The spectrum blends
+/// blue, green and red basis functions that sum to one everywhere, so white stays flat and
+/// reflectances in [0, 1] stay in [0, 1]
+pub fn rgb_to_spectrum(rgb: &Colour, wavelength: f64) -> f64 {
+    let smoothstep = |edge0: f64, edge1: f64| {
+        let t = ((wavelength - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+        t * t * (3.0 - 2.0 * t)
+    };
+
+    let blue = 1.0 - smoothstep(480.0, 520.0);
+    let red = smoothstep(570.0, 610.0);
+    let green = 1.0 - blue - red;
+
+    rgb.r() * red + rgb.g() * green + rgb.b() * blue
+}
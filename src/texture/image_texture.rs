@@ -0,0 +1,68 @@
+use std::path::Path;
+
+use image::RgbImage;
+
+use crate::{
+    util::Interval,
+    vec3::{Colour, Point3},
+};
+
+use super::Texture;
+
+/// Texture looked up from an image by the hit's u/v coordinates
+#[derive(Debug, Clone)]
+pub struct ImageTexture {
+    image: RgbImage,
+    gamma_encoded: bool,
+}
+
+impl ImageTexture {
+    /// Loads a colour image, converting from the output gamma back to linear
+    pub fn from<P>(filename: P) -> anyhow::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        Ok(Self {
+            image: image::open(filename)?.to_rgb8(),
+            gamma_encoded: true,
+        })
+    }
+
+    /// Loads an image that stores raw data such as normals or heights rather than colours
+    pub fn from_linear<P>(filename: P) -> anyhow::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        Ok(Self {
+            image: image::open(filename)?.to_rgb8(),
+            gamma_encoded: false,
+        })
+    }
+}
+
+impl Texture for ImageTexture {
+    fn value(&self, u: f64, v: f64, #[allow(unused_variables)] p: &Point3) -> Colour {
+        if self.image.width() == 0 || self.image.height() == 0 {
+            return Colour::new(0.0, 1.0, 1.0);
+        }
+
+        // Flip v so the image is upright
+        let unit = Interval::new(0.0, 1.0);
+        let u = unit.clamp(u);
+        let v = 1.0 - unit.clamp(v);
+
+        let x = ((u * self.image.width() as f64) as u32).min(self.image.width() - 1);
+        let y = ((v * self.image.height() as f64) as u32).min(self.image.height() - 1);
+        let pixel = self.image.get_pixel(x, y);
+
+        let decode = |c: u8| {
+            let c = c as f64 / 255.0;
+            if self.gamma_encoded {
+                c * c
+            } else {
+                c
+            }
+        };
+        Colour::new(decode(pixel[0]), decode(pixel[1]), decode(pixel[2]))
+    }
+}
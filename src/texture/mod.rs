@@ -0,0 +1,17 @@
+use std::fmt::Debug;
+
+use crate::{
+    mod_flat,
+    vec3::{Colour, Point3},
+};
+
+mod_flat!(checker image_texture solid_colour);
+
+pub trait Texture: Sync + Send + Debug {
+    fn value(&self, u: f64, v: f64, p: &Point3) -> Colour;
+
+    /// Single channel lookup for textures driving scalar parameters such as roughness
+    fn scalar(&self, u: f64, v: f64, p: &Point3) -> f64 {
+        self.value(u, v, p).r()
+    }
+}
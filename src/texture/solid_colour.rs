@@ -0,0 +1,30 @@
+use crate::vec3::{Colour, Point3};
+
+use super::Texture;
+
+#[derive(Debug, Clone)]
+pub struct SolidColour {
+    albedo: Colour,
+}
+
+impl SolidColour {
+    pub fn new(albedo: Colour) -> Self {
+        Self { albedo }
+    }
+
+    /// Constant texture with every channel set to `value`
+    pub fn scalar(value: f64) -> Self {
+        Self::new(Colour::new(value, value, value))
+    }
+}
+
+impl Texture for SolidColour {
+    fn value(
+        &self,
+        #[allow(unused_variables)] u: f64,
+        #[allow(unused_variables)] v: f64,
+        #[allow(unused_variables)] p: &Point3,
+    ) -> Colour {
+        self.albedo
+    }
+}
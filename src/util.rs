@@ -10,6 +10,7 @@ pub fn random_real() -> f64 {
     rand::rng().random_range(0.0..1.0)
 }
 
+#[derive(Clone, Copy, Debug)]
 pub struct Interval {
     pub min: f64,
     pub max: f64,
@@ -30,6 +31,14 @@ impl Interval {
         Self { min, max }
     }
 
+    /// Smallest interval containing both `a` and `b`
+    pub fn enclosing(a: &Interval, b: &Interval) -> Self {
+        Self {
+            min: a.min.min(b.min),
+            max: a.max.max(b.max),
+        }
+    }
+
     pub fn size(&self) -> f64 {
         self.max - self.min
     }
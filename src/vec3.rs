@@ -58,7 +58,11 @@ impl Point3 {
 
     pub fn random_unit_vector() -> Self {
         loop {
-            let p = Self::random_real();
+            let p = Vec3::new(
+                rand::rng().random_range(-1.0..=1.0),
+                rand::rng().random_range(-1.0..=1.0),
+                rand::rng().random_range(-1.0..=1.0),
+            );
             let lensq = p.length_squared();
             if 1e-160 < lensq && lensq <= 1.0 {
                 return p / lensq.sqrt();